@@ -1,12 +1,11 @@
 #![no_std]
 
-use soroban_sdk::{
-    contract, contractimpl, contracttype, panic_with_error, symbol_short, Address, BytesN, Env, IntoVal, Map, storage,
-    Vec,
-};
+use soroban_sdk::{contract, contracterror, contractimpl, contracttype, panic_with_error, Address, Env};
 
 /// Errors for the SkillTree contract.
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
 pub enum Error {
     /// Quest already completed by user.
     QuestAlreadyCompleted = 1,
@@ -18,209 +17,225 @@ pub enum Error {
     BadgeMintFailed = 4,
     /// Character already minted for user.
     CharacterAlreadyMinted = 5,
+    /// Contract already initialized.
+    AlreadyInitialized = 6,
+    /// Badge does not exist.
+    BadgeNotFound = 7,
+    /// Only the badge owner can transfer it.
+    NotOwner = 8,
 }
 
-impl soroban_sdk::contracterror::ContractError for Error {
-    fn as_u32(&self) -> u32 {
-        *self as u32
-    }
+/// Storage keys
+#[contracttype]
+#[derive(Clone)]
+enum DataKey {
+    Admin,
+    CharacterMinted(Address),
+    QuestCompleted(Address, u32),
+    BadgeCount,
+    Badge(u128),
 }
 
-/// Quest info struct for quest metadata (optional, stored in contract)
-#[derive(Clone)]
-pub struct Quest {
-    pub id: u32,
-    pub name: &'static str,
-    pub badge_name: &'static str,
+/// A badge NFT: who holds it and which quest it was earned for
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Badge {
+    pub owner: Address,
+    pub quest_id: u32,
 }
 
+/// Badge token IDs are `quest_id * BADGE_ID_QUEST_FACTOR + serial`
+pub const BADGE_ID_QUEST_FACTOR: u128 = 1_000_000_000_000;
+/// Quests are numbered 1 through MAX_QUEST_ID
+pub const MAX_QUEST_ID: u32 = 5;
+
 /// Main contract struct
+#[contract]
 pub struct SkillTreeContract;
 
-const CHARACTER_NFT_NAME: &str = "SkillTree Character";
-const CHARACTER_NFT_SYMBOL: &str = "STC";
-
-const BADGE_NFT_NAME: &str = "SkillTree Badge";
-const BADGE_NFT_SYMBOL: &str = "STB";
-
-const KEY_ADMIN: &str = "admin";
-const KEY_CHARACTER_MINTED: &str = "character_minted";
-const PREFIX_QUEST_COMPLETED: &str = "quest_completed";
-
-// We will use Soroban token contract for NFTs.
-// We need the character soulbound NFT (non-transferable) - we simulate this by preventing transfers.
-// Badges are transferable NFTs minted upon quest completion.
+// The character NFT is soulbound (non-transferable): it is only recorded per user.
+// Badges are transferable NFTs minted upon quest completion, so they can be traded
+// (e.g. through the escrow marketplace). Because they carry value, only the admin (the
+// quest logic) mints them; users cannot self-attest a completed quest.
 
 #[contractimpl]
 impl SkillTreeContract {
-    /// Initialize contract with admin set to caller.
-    pub fn initialize(env: Env) {
-        let admin = env.invoker();
-        env.storage().set(&symbol!(KEY_ADMIN), &admin);
+    /// Initialize contract with an admin.
+    pub fn initialize(env: Env, admin: Address) {
+        if env.storage().instance().has(&DataKey::Admin) {
+            panic_with_error!(&env, Error::AlreadyInitialized);
+        }
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Admin, &admin);
     }
 
     /// Mint a soulbound character NFT for the user.
     /// Only one character NFT per user, soulbound means non-transferable.
-    pub fn mint_character(env: Env, user: Address) {
+    pub fn mint_character(env: Env, caller: Address, user: Address) {
         // Only user themselves can mint their character or admin
-        let invoker = env.invoker();
-        if invoker != user && invoker != Self::admin(&env) {
-            panic_with_error!(&env, Error::Unauthorized);
-        }
+        Self::require_user_or_admin(&env, &caller, &user);
 
-        // Check if character NFT already minted for user
-        let storage = env.storage();
-        let char_key = (symbol!(KEY_CHARACTER_MINTED), user.clone());
-        if storage.has(&char_key) {
+        let key = DataKey::CharacterMinted(user);
+        if env.storage().persistent().has(&key) {
             panic_with_error!(&env, Error::CharacterAlreadyMinted);
         }
+        env.storage().persistent().set(&key, &true);
+    }
 
-        // Use token interface to mint soulbound NFT - character
-        // Create a token id for character NFT collection (unique)
-        // For simplicity, use contract id + "character" as TokenIdentifier
-        let token_id = Self::character_token_id(&env);
-
-        // Create metadata attribute map for character NFT
-        let mut metadatas = Map::<symbol::Symbol, Vec<u8>>::new(&env);
-        metadatas.set(symbol!("name"), CHARACTER_NFT_NAME.into_val(&env));
-        metadatas.set(symbol!("symbol"), CHARACTER_NFT_SYMBOL.into_val(&env));
-
-        // Instantiate token object for mint operation
-        let token_client = token::Client::new(&env, &token_id);
-
-        // Mint token id #user address represented as uint256 or bytes as token ID
-        // Here we mint a single NFT with token_id and token ID is 1 for user
-        // But NFTs in Soroban tokens are identified by (TokenIdentifier, u128)
-        // We'll use user's address as u128 hash for token ID for uniqueness
-
-        let user_id_bytes = user.to_bytes(&env);
-        let user_id_num = Self::address_to_u128(&user_id_bytes);
-
-        token_client.mint(&user, &user_id_num, &1);
-
-        // Store that character minted for user
-        storage.set(&char_key, &true);
+    /// Check if a user has minted their character
+    pub fn has_character(env: Env, user: Address) -> bool {
+        env.storage().persistent().has(&DataKey::CharacterMinted(user))
     }
 
     /// Mint a badge NFT for a user when they complete a quest.
     /// Each badge is an NFT with unique ID.
     /// QuestId is the identifier for the quest.
-    pub fn mint_badge(env: Env, user: Address, quest_id: u32) {
-        let invoker = env.invoker();
-        // Only user themselves or admin can mint badges for user
-        if invoker != user && invoker != Self::admin(&env) {
+    /// Only the admin can mint badges, once it has verified the quest was completed.
+    pub fn mint_badge(env: Env, caller: Address, user: Address, quest_id: u32) -> u128 {
+        caller.require_auth();
+        if caller != Self::admin_or_panic(&env) {
             panic_with_error!(&env, Error::Unauthorized);
         }
 
         // Check quest completion state to prevent double minting
-        let storage = env.storage();
-        let quest_completed_key = (symbol!(PREFIX_QUEST_COMPLETED), user.clone(), quest_id);
-        if storage.has(&quest_completed_key) {
+        let completed_key = DataKey::QuestCompleted(user.clone(), quest_id);
+        if env.storage().persistent().has(&completed_key) {
             panic_with_error!(&env, Error::QuestAlreadyCompleted);
         }
 
         // Verify quest exists - in a real app you might store quests; here we accept 1 through 5
-        if quest_id == 0 || quest_id > 5 {
+        if quest_id == 0 || quest_id > MAX_QUEST_ID {
             panic_with_error!(&env, Error::QuestNotFound);
         }
 
-        // Mint badge NFT to user
-        let token_id = Self::badge_token_id(&env);
-        let token_client = token::Client::new(&env, &token_id);
+        // Token ID embeds the quest so it can be read back from the ID alone
+        let serial: u128 = env.storage().instance().get(&DataKey::BadgeCount).unwrap_or(0) + 1;
+        env.storage().instance().set(&DataKey::BadgeCount, &serial);
+        let token_id = (quest_id as u128) * BADGE_ID_QUEST_FACTOR + serial;
 
-        // Token ID: combine quest_id + user_address hash to generate unique token ID for each badge
-        // For simplicity, token ID = quest_id * 1_000_000_000_000 + user numeric ID (u128)
-        let user_id_bytes = user.to_bytes(&env);
-        let user_id_num = Self::address_to_u128(&user_id_bytes);
-        let token_number = (quest_id as u128) * 1_000_000_000_000u128 + user_id_num;
-
-        token_client.mint(&user, &token_number, &1);
+        env.storage().persistent().set(&DataKey::Badge(token_id), &Badge { owner: user, quest_id });
 
         // Store quest completion
-        storage.set(&quest_completed_key, &true);
+        env.storage().persistent().set(&completed_key, &true);
 
-        // Update user's badge count (optional)
-        // Not implemented here; can be derived offchain by counting NFTs
+        token_id
     }
 
-    /// Helper to get admin address
-    pub fn admin(env: &Env) -> Address {
-        env.storage()
-            .get_unchecked::<_, Address>(&symbol!(KEY_ADMIN))
-            .unwrap()
+    /// Transfer a badge; `from` must own it and authorize
+    pub fn transfer(env: Env, from: Address, to: Address, token_id: u128) {
+        from.require_auth();
+
+        let mut badge = Self::badge_or_panic(&env, token_id);
+        if badge.owner != from {
+            panic_with_error!(&env, Error::NotOwner);
+        }
+        badge.owner = to;
+        env.storage().persistent().set(&DataKey::Badge(token_id), &badge);
+    }
+
+    /// Current holder of a badge
+    pub fn owner_of(env: Env, token_id: u128) -> Address {
+        Self::badge_or_panic(&env, token_id).owner
+    }
+
+    /// Quest the badge was issued for
+    pub fn quest_id(env: Env, token_id: u128) -> u32 {
+        Self::badge_or_panic(&env, token_id).quest_id
+    }
+
+    /// Get the admin address
+    pub fn admin(env: Env) -> Address {
+        Self::admin_or_panic(&env)
     }
 
-    /// Helper to get character token identifier for soulbound character NFTs.
-    /// We simulate an NFT collection by contract id + suffix "character"
-    pub fn character_token_id(env: &Env) -> TokenIdentifier {
-        let mut contractid = env.get_current_contract_id().into_val(env);
-        // Append "character" bytes
-        contractid.extend_from_slice(b"character");
-        TokenIdentifier::from_bytes(&env, &contractid)
+    fn admin_or_panic(env: &Env) -> Address {
+        env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .unwrap_or_else(|| panic_with_error!(env, Error::Unauthorized))
     }
 
-    /// Helper to get badge token identifier for badge NFTs.
-    pub fn badge_token_id(env: &Env) -> TokenIdentifier {
-        let mut contractid = env.get_current_contract_id().into_val(env);
-        // Append "badge" bytes
-        contractid.extend_from_slice(b"badge");
-        TokenIdentifier::from_bytes(&env, &contractid)
+    fn require_user_or_admin(env: &Env, caller: &Address, user: &Address) {
+        caller.require_auth();
+        if caller != user && *caller != Self::admin_or_panic(env) {
+            panic_with_error!(env, Error::Unauthorized);
+        }
     }
 
-    /// Convert address bytes to u128 for token IDs
-    pub fn address_to_u128(bytes: &BytesN<32>) -> u128 {
-        // Take first 16 bytes as u128 in big endian
-        let slice = &bytes.as_ref()[..16];
-        let mut arr = [0u8; 16];
-        arr.copy_from_slice(slice);
-        u128::from_be_bytes(arr)
+    fn badge_or_panic(env: &Env, token_id: u128) -> Badge {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Badge(token_id))
+            .unwrap_or_else(|| panic_with_error!(env, Error::BadgeNotFound))
     }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
-    use soroban_sdk::{testutils::Env as TestEnv, Address, BytesN};
+    use soroban_sdk::testutils::Address as _;
+
+    fn setup(env: &Env) -> (Address, SkillTreeContractClient<'_>) {
+        env.mock_all_auths();
+        let client = SkillTreeContractClient::new(env, &env.register(SkillTreeContract, ()));
+        let admin = Address::generate(env);
+        client.initialize(&admin);
+        (admin, client)
+    }
 
     #[test]
     fn test_initialize_and_admin() {
-        let env = TestEnv::default();
-        let contract = SkillTreeContract {};
-        // initialize
-        contract.initialize(env.clone());
-        let admin = SkillTreeContract::admin(&env);
-        assert_eq!(admin, env.invoker());
+        let env = Env::default();
+        let (admin, client) = setup(&env);
+        assert_eq!(client.admin(), admin);
+        assert_eq!(client.try_initialize(&admin), Err(Ok(Error::AlreadyInitialized.into())));
     }
 
     #[test]
     fn test_mint_character_works() {
-        let env = TestEnv::default();
-        let contract = SkillTreeContract {};
-        contract.initialize(env.clone());
-        let user = Address::random(&env);
-        env.set_invoker(user.clone());
-        contract.mint_character(env.clone(), user.clone());
+        let env = Env::default();
+        let (_, client) = setup(&env);
+        let user = Address::generate(&env);
+
+        client.mint_character(&user, &user);
+        assert!(client.has_character(&user));
         // minting again should fail
-        let result =
-            std::panic::catch_unwind(|| contract.mint_character(env.clone(), user.clone()));
-        assert!(result.is_err());
+        assert_eq!(client.try_mint_character(&user, &user), Err(Ok(Error::CharacterAlreadyMinted.into())));
     }
 
     #[test]
     fn test_mint_badge_works() {
-        let env = TestEnv::default();
-        let contract = SkillTreeContract {};
-        contract.initialize(env.clone());
-        let user = Address::random(&env);
-        env.set_invoker(user.clone());
+        let env = Env::default();
+        let (admin, client) = setup(&env);
+        let user = Address::generate(&env);
 
-        contract.mint_badge(env.clone(), user.clone(), 1);
+        let token_id = client.mint_badge(&admin, &user, &1);
+        assert_eq!(client.owner_of(&token_id), user);
+        assert_eq!(client.quest_id(&token_id), 1);
         // minting same badge again fails
-        let result = std::panic::catch_unwind(|| contract.mint_badge(env.clone(), user.clone(), 1));
-        assert!(result.is_err());
+        assert_eq!(client.try_mint_badge(&admin, &user, &1), Err(Ok(Error::QuestAlreadyCompleted.into())));
         // minting badge for invalid quest fails
-        let result2 =
-            std::panic::catch_unwind(|| contract.mint_badge(env.clone(), user.clone(), 10));
-        assert!(result2.is_err());
+        assert_eq!(client.try_mint_badge(&admin, &user, &10), Err(Ok(Error::QuestNotFound.into())));
+        // only the admin can mint, not even the user for themselves
+        assert_eq!(client.try_mint_badge(&user, &user, &2), Err(Ok(Error::Unauthorized.into())));
+        let other = Address::generate(&env);
+        assert_eq!(client.try_mint_badge(&other, &user, &2), Err(Ok(Error::Unauthorized.into())));
+    }
+
+    #[test]
+    fn test_transfer_badge() {
+        let env = Env::default();
+        let (admin, client) = setup(&env);
+        let user = Address::generate(&env);
+        let buyer = Address::generate(&env);
+
+        let token_id = client.mint_badge(&admin, &user, &3);
+        client.transfer(&user, &buyer, &token_id);
+        assert_eq!(client.owner_of(&token_id), buyer);
+        assert_eq!(client.quest_id(&token_id), 3);
+
+        // the previous owner can no longer move it
+        assert_eq!(client.try_transfer(&user, &user, &token_id), Err(Ok(Error::NotOwner.into())));
+        assert_eq!(client.try_transfer(&user, &buyer, &42), Err(Ok(Error::BadgeNotFound.into())));
     }
 }
@@ -1,3 +1,5 @@
+#![allow(dead_code)]
+
 use soroban_sdk::{Address, Env, Symbol, symbol_short, Map};
 
 const ADMIN_KEY: Symbol = symbol_short!("admin");
@@ -1,5 +1,18 @@
-use soroban_sdk::{Address, Env};
+use soroban_sdk::{contractclient, Address, Env};
 
-pub trait CrossContractTrait {
-    fn adjust_score_with_oracle(&self, env: Env, user: Address, oracle_contract: Address);
-}
\ No newline at end of file
+/// Reputation scale returned by the reputation oracle
+pub const MAX_REPUTATION: u32 = 1_000;
+/// Points added to the base score at full reputation
+pub const MAX_REPUTATION_BONUS: u32 = 100;
+
+/// Interface the credit score expects from the reputation oracle contract
+#[contractclient(name = "ReputationOracleClient")]
+pub trait ReputationOracleInterface {
+    /// Reputation of an account in `0..=MAX_REPUTATION`
+    fn get_reputation(env: Env, account: Address) -> u32;
+}
+
+/// Scale an oracle reputation to a credit score bonus
+pub fn reputation_factor(reputation: u32) -> u32 {
+    reputation.min(MAX_REPUTATION) * MAX_REPUTATION_BONUS / MAX_REPUTATION
+}
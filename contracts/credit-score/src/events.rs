@@ -1,17 +1,43 @@
-use soroban_sdk::{Env, Address, Symbol, symbol_short};
+use soroban_sdk::{Env, Address, BytesN, Symbol, symbol_short};
 
 const TOPIC_SCORE_UPDATE: Symbol = symbol_short!("sc_upd");
 const TOPIC_UPGRADE: Symbol = symbol_short!("upgd");
+const TOPIC_DISPUTE_FILED: Symbol = symbol_short!("sc_disp");
+const TOPIC_DISPUTE_RESOLVED: Symbol = symbol_short!("sc_res");
+const TOPIC_ATTESTATION: Symbol = symbol_short!("attest");
+const TOPIC_CONSENT: Symbol = symbol_short!("consent");
+const TOPIC_INCREASE_CAPPED: Symbol = symbol_short!("sc_cap");
 
 pub fn emit_score_updated(env: &Env, user: &Address, new_score: &i128) {
     env.events().publish((TOPIC_SCORE_UPDATE, user.clone()), *new_score);
 }
 
+pub fn emit_dispute_filed(env: &Env, user: &Address, checkpoint_index: u32, reason_hash: &BytesN<32>) {
+    env.events().publish((TOPIC_DISPUTE_FILED, user.clone()), (checkpoint_index, reason_hash.clone()));
+}
+
+pub fn emit_dispute_resolved(env: &Env, user: &Address, upheld: bool, new_score: &i128) {
+    env.events().publish((TOPIC_DISPUTE_RESOLVED, user.clone()), (upheld, *new_score));
+}
+
+pub fn emit_attestation(env: &Env, user: &Address, id: &BytesN<32>, threshold: u32, expires_at: u32) {
+    env.events().publish((TOPIC_ATTESTATION, user.clone()), (id.clone(), threshold, expires_at));
+}
+
+pub fn emit_consent_changed(env: &Env, user: &Address, reader: &Address, granted: bool) {
+    env.events().publish((TOPIC_CONSENT, user.clone()), (reader.clone(), granted));
+}
+
+pub fn emit_increase_capped(env: &Env, user: &Address, requested: &i128, applied: &i128) {
+    env.events().publish((TOPIC_INCREASE_CAPPED, user.clone()), (*requested, *applied));
+}
+
 pub fn emit_upgraded(env: &Env, version: &u32) {
     env.events().publish(((TOPIC_UPGRADE,),), *version);
 }
 
 // For "handling": Off-chain via RPC, but stub for in-contract log if needed
+#[allow(dead_code)]
 pub fn log_event(env: &Env, topic: Symbol, data: i128) {
     // Could extend to temp storage log for debugging
     env.storage().temporary().set(&topic, &data);
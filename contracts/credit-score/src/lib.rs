@@ -1,16 +1,26 @@
 #![no_std]
 
-use soroban_sdk::{contract, contractimpl, Address, Env, BytesN, String, symbol_short, Vec};
+use soroban_sdk::{contract, contractimpl, panic_with_error, xdr::ToXdr, Address, Bytes, Env, BytesN, String, symbol_short, Vec};
 
 mod access_control;
+mod cross_contract;
 mod events;
 mod storage;
 mod upgrade;
 
-use crate::storage::{get_score, set_score, has_score};
-use crate::events::emit_score_updated;
+pub use crate::cross_contract::{ReputationOracleInterface, MAX_REPUTATION_BONUS};
+
+use crate::cross_contract::{reputation_factor, ReputationOracleClient};
+use crate::storage::{get_score, set_score, has_score, get_reputation_oracle, set_reputation_oracle, get_checkpoint, get_checkpoint_count};
+
+pub use crate::storage::{IncreaseCap, ScoreAttestation, ScoreCheckpoint, ScoreDispute};
+pub use crate::upgrade::Error;
+use crate::events::{emit_attestation, emit_consent_changed, emit_dispute_filed, emit_increase_capped, emit_dispute_resolved, emit_score_updated};
 use crate::upgrade::{init_admin, upgrade as perform_upgrade};
 
+/// Longest validity of a score attestation (~30 days of 5s ledgers)
+pub const MAX_ATTESTATION_TTL: u32 = 518_400;
+
 #[contract]
 pub struct CreditScoreContract;
 
@@ -26,40 +36,42 @@ impl CreditScoreContract {
         env.storage().instance().set(&factors_key, &default_factors);
     }
 
-    /// Calculate credit score for an account, potentially using cross-contract oracle
-    pub fn calculate_score(_env: Env, account: Address) -> u32 {
+    /// Calculate credit score for an account, including the reputation oracle factor when configured
+    pub fn calculate_score(env: Env, account: Address) -> u32 {
         account.require_auth();
         let base_score: u32 = 600;  // Default base
-        // TODO: Implement cross-contract oracle adjustment (uncomment when oracle.wasm ready)
-        // mod oracle {
-        //     soroban_sdk::contractimport!(file = "../oracle/target/wasm32-unknown-unknown/release/oracle.wasm");
-        // }
-        // use oracle::Client as OracleClient;
-        // let oracle_id = String::from_str(&env, "oracle_contract_id_here");
-        // let oracle_contract = Address::from_string(&env, &oracle_id);
-        // let oracle_client = OracleClient::new(&env, &oracle_contract);
-        // if let Ok(adjustment) = oracle_client.get_adjustment(&account) {
-        //     let adjusted = ((base_score as i128) + adjustment).max(0) as u32;
-        //     let score_i128 = adjusted as i128;
-        //     set_score(&env, &account, &score_i128);
-        //     emit_score_updated(&env, &account, &score_i128);
-        //     return adjusted;
-        // }
-        // Fallback
-        base_score
-    }
-
-    /// Get credit score for an account
+        match get_reputation_oracle(&env) {
+            Some(oracle) => {
+                let reputation = ReputationOracleClient::new(&env, &oracle).get_reputation(&account);
+                base_score + reputation_factor(reputation)
+            }
+            None => base_score,
+        }
+    }
+
+    /// Set the reputation oracle consumed by `calculate_score` (admin only)
+    pub fn set_reputation_oracle(env: Env, admin: Address, oracle: Address) {
+        upgrade::require_admin(&env, &admin);
+        set_reputation_oracle(&env, &oracle);
+    }
+
+    /// Get the configured reputation oracle, if any
+    pub fn get_reputation_oracle(env: Env) -> Option<Address> {
+        get_reputation_oracle(&env)
+    }
+
+    /// Get credit score for an account (account auth; third parties use `get_score_for`)
     pub fn get_score(env: Env, account: Address) -> u32 {
-        let score = get_score(&env, &account);
-        if score < 0 { 0u32 } else { score.min(i128::from(u32::MAX)) as u32 }
+        account.require_auth();
+        Self::read_score(&env, &account)
     }
 
     /// Update credit score factors
     pub fn update_factors(env: Env, account: Address, factors_str: String) {
         account.require_auth();  // Auth as account owner
 
-        let mut score = get_score(&env, &account);
+        let current = get_score(&env, &account);
+        let mut score = current;
         // Simplified: Direct string equality
         let boost_str = String::from_str(&env, "boost");
         let penalty_str = String::from_str(&env, "penalty");
@@ -68,6 +80,8 @@ impl CreditScoreContract {
         } else if factors_str == penalty_str {
             score = score.checked_sub(50).unwrap_or(score).max(0i128);
         }
+        score = Self::cap_increase(&env, &account, current, score);
+        Self::require_not_frozen(&env, &account, score);
         set_score(&env, &account, &score);
         emit_score_updated(&env, &account, &score);
     }
@@ -79,8 +93,9 @@ impl CreditScoreContract {
         // Stub: Simulate oracle adjustment (replace with real client call)
         let adjustment: i128 = 10;  // Mock
 
-        let mut score = get_score(&env, &user);
-        score = score.checked_add(adjustment).expect("score overflow");
+        let current = get_score(&env, &user);
+        let mut score = current.checked_add(adjustment).expect("score overflow");
+        score = Self::cap_increase(&env, &user, current, score);
         set_score(&env, &user, &score);
         emit_score_updated(&env, &user, &score);
     }
@@ -119,6 +134,248 @@ impl CreditScoreContract {
     pub fn has_score(env: Env, account: Address) -> bool {
         has_score(&env, &account)
     }
+
+    /// Number of score checkpoints recorded for an account
+    pub fn get_checkpoint_count(env: Env, account: Address) -> u32 {
+        get_checkpoint_count(&env, &account)
+    }
+
+    /// Get a score checkpoint by index (0 = oldest; account auth)
+    pub fn get_checkpoint(env: Env, account: Address, index: u32) -> Option<ScoreCheckpoint> {
+        account.require_auth();
+        get_checkpoint(&env, &account, index)
+    }
+
+    /// Average score over the last `window_ledgers` ledgers, weighted by how long each score was held.
+    /// Ledgers before the account's first checkpoint count as a score of 0. Accounts scored before
+    /// checkpoints existed have held their current score throughout. Requires the account's auth.
+    pub fn get_time_weighted_score(env: Env, account: Address, window_ledgers: u32) -> u32 {
+        account.require_auth();
+        Self::read_time_weighted_score(&env, &account, window_ledgers)
+    }
+
+    /// Time-weighted score read on behalf of `reader`, who must hold the account's consent
+    pub fn get_time_weighted_score_for(env: Env, reader: Address, account: Address, window_ledgers: u32) -> u32 {
+        reader.require_auth();
+        if !Self::has_consent(env.clone(), account.clone(), reader) {
+            panic_with_error!(&env, Error::NoConsent);
+        }
+        Self::read_time_weighted_score(&env, &account, window_ledgers)
+    }
+
+    fn read_time_weighted_score(env: &Env, account: &Address, window_ledgers: u32) -> u32 {
+        let end = env.ledger().sequence();
+        let start = end.saturating_sub(window_ledgers);
+        if start == end || get_checkpoint_count(env, account) == 0 {
+            return Self::read_score(env, account);
+        }
+
+        let weighted = storage::cumulative_score_at(env, account, end)
+            - storage::cumulative_score_at(env, account, start);
+
+        let average = weighted / i128::from(end - start);
+        if average < 0 { 0u32 } else { average.min(i128::from(u32::MAX)) as u32 }
+    }
+
+    /// Set the arbiter allowed to resolve disputes alongside the admin (admin only)
+    pub fn set_arbiter(env: Env, admin: Address, arbiter: Address) {
+        upgrade::require_admin(&env, &admin);
+        storage::set_arbiter(&env, &arbiter);
+    }
+
+    /// Get the dispute arbiter, if any
+    pub fn get_arbiter(env: Env) -> Option<Address> {
+        storage::get_arbiter(&env)
+    }
+
+    /// Dispute the score change recorded at `checkpoint_index`.
+    /// Downward adjustments of the account's score are frozen until the dispute is resolved.
+    /// Each checkpoint can only be disputed until a dispute against it has been resolved.
+    pub fn dispute_score_change(env: Env, account: Address, checkpoint_index: u32, reason_hash: BytesN<32>) {
+        account.require_auth();
+
+        if storage::get_dispute(&env, &account).is_some() {
+            panic_with_error!(&env, Error::DisputeOpen);
+        }
+        if storage::is_dispute_resolved(&env, &account, checkpoint_index) {
+            panic_with_error!(&env, Error::DisputeResolved);
+        }
+        let checkpoint = get_checkpoint(&env, &account, checkpoint_index)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::CheckpointNotFound));
+        let previous_score = match checkpoint_index.checked_sub(1) {
+            Some(previous) => get_checkpoint(&env, &account, previous).unwrap().score,
+            None => 0,
+        };
+
+        storage::set_dispute(&env, &account, &ScoreDispute {
+            checkpoint_index,
+            reason_hash: reason_hash.clone(),
+            previous_score,
+            disputed_score: checkpoint.score,
+            filed_at: env.ledger().sequence(),
+        });
+        emit_dispute_filed(&env, &account, checkpoint_index, &reason_hash);
+    }
+
+    /// Resolve an open dispute (admin or arbiter). Upholding it reverses the disputed change
+    /// on top of the current score; rejecting it only lifts the freeze.
+    pub fn resolve_dispute(env: Env, resolver: Address, account: Address, upheld: bool) {
+        resolver.require_auth();
+        let is_arbiter = storage::get_arbiter(&env) == Some(resolver.clone());
+        if !is_arbiter && !upgrade::is_admin(&env, &resolver) {
+            panic_with_error!(&env, Error::NotArbiter);
+        }
+
+        let dispute = storage::get_dispute(&env, &account)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NoOpenDispute));
+        storage::remove_dispute(&env, &account);
+        storage::set_dispute_resolved(&env, &account, dispute.checkpoint_index);
+
+        let mut score = get_score(&env, &account);
+        if upheld {
+            score = score
+                .checked_add(dispute.previous_score - dispute.disputed_score)
+                .unwrap_or(score)
+                .max(0i128);
+            set_score(&env, &account, &score);
+            emit_score_updated(&env, &account, &score);
+        }
+        emit_dispute_resolved(&env, &account, upheld, &score);
+    }
+
+    /// Get the open dispute for an account, if any. It holds the disputed scores, so `reader`
+    /// must be the account, a reader it consented to, the admin or the arbiter.
+    pub fn get_dispute(env: Env, reader: Address, account: Address) -> Option<ScoreDispute> {
+        reader.require_auth();
+        let is_resolver = upgrade::is_admin(&env, &reader) || storage::get_arbiter(&env) == Some(reader.clone());
+        if !is_resolver && !Self::has_consent(env.clone(), account.clone(), reader) {
+            panic_with_error!(&env, Error::NoConsent);
+        }
+        storage::get_dispute(&env, &account)
+    }
+
+    /// Attest that the account's score is at least `threshold`. The attestation records only the
+    /// threshold, so a verifier learns nothing more from it; the score itself is still public ledger data.
+    /// Returns the attestation id a third party can check with `verify_attestation`.
+    pub fn attest_score_threshold(env: Env, account: Address, threshold: u32, ttl_ledgers: u32) -> BytesN<32> {
+        account.require_auth();
+
+        if ttl_ledgers == 0 || ttl_ledgers > MAX_ATTESTATION_TTL {
+            panic_with_error!(&env, Error::InvalidTtl);
+        }
+        if Self::read_score(&env, &account) < threshold {
+            panic_with_error!(&env, Error::BelowThreshold);
+        }
+
+        let nonce = storage::next_attestation_nonce(&env, &account);
+        let mut preimage = Bytes::new(&env);
+        preimage.append(&env.current_contract_address().to_xdr(&env));
+        preimage.append(&account.clone().to_xdr(&env));
+        preimage.extend_from_array(&nonce.to_be_bytes());
+        let id: BytesN<32> = env.crypto().sha256(&preimage).into();
+
+        let issued_at = env.ledger().sequence();
+        let expires_at = issued_at + ttl_ledgers;
+        storage::set_attestation(&env, &id, &ScoreAttestation {
+            account: account.clone(),
+            threshold,
+            issued_at,
+            expires_at,
+        });
+        emit_attestation(&env, &account, &id, threshold, expires_at);
+
+        id
+    }
+
+    /// Get an attestation if it exists and has not expired
+    pub fn verify_attestation(env: Env, id: BytesN<32>) -> Option<ScoreAttestation> {
+        storage::get_attestation(&env, &id)
+            .filter(|attestation| env.ledger().sequence() <= attestation.expires_at)
+    }
+
+    /// Allow `reader` to read the account's score through `get_score_for`
+    pub fn grant_consent(env: Env, account: Address, reader: Address) {
+        account.require_auth();
+        storage::set_consent(&env, &account, &reader);
+        emit_consent_changed(&env, &account, &reader, true);
+    }
+
+    /// Withdraw a reader's permission to read the account's score
+    pub fn revoke_consent(env: Env, account: Address, reader: Address) {
+        account.require_auth();
+        storage::remove_consent(&env, &account, &reader);
+        emit_consent_changed(&env, &account, &reader, false);
+    }
+
+    /// Check whether `reader` may read the account's score
+    pub fn has_consent(env: Env, account: Address, reader: Address) -> bool {
+        reader == account || storage::has_consent(&env, &account, &reader)
+    }
+
+    /// Read an account's score on behalf of `reader`, who must hold the account's consent
+    pub fn get_score_for(env: Env, reader: Address, account: Address) -> u32 {
+        reader.require_auth();
+        if !Self::has_consent(env.clone(), account.clone(), reader) {
+            panic_with_error!(&env, Error::NoConsent);
+        }
+        Self::read_score(&env, &account)
+    }
+
+    /// Cap how much any account's score can rise per epoch of `epoch_ledgers` (admin only).
+    /// Increases beyond the cap are clamped.
+    pub fn set_increase_cap(env: Env, admin: Address, epoch_ledgers: u32, max_increase: u32) {
+        upgrade::require_admin(&env, &admin);
+        if epoch_ledgers == 0 {
+            panic_with_error!(&env, Error::InvalidEpoch);
+        }
+        storage::set_increase_cap(&env, &IncreaseCap { epoch_ledgers, max_increase });
+    }
+
+    /// Remove the per-epoch increase cap (admin only)
+    pub fn clear_increase_cap(env: Env, admin: Address) {
+        upgrade::require_admin(&env, &admin);
+        storage::remove_increase_cap(&env);
+    }
+
+    /// Get the per-epoch increase cap, if any
+    pub fn get_increase_cap(env: Env) -> Option<IncreaseCap> {
+        storage::get_increase_cap(&env)
+    }
+
+    /// Stored score clamped to the u32 range of the public getters
+    fn read_score(env: &Env, account: &Address) -> u32 {
+        let score = get_score(env, account);
+        if score < 0 { 0u32 } else { score.min(i128::from(u32::MAX)) as u32 }
+    }
+
+    /// Clamp a score increase to what is left of the account's allowance for this epoch
+    fn cap_increase(env: &Env, account: &Address, current: i128, new_score: i128) -> i128 {
+        let cap = match storage::get_increase_cap(env) {
+            Some(cap) if new_score > current => cap,
+            _ => return new_score,
+        };
+
+        let epoch = env.ledger().sequence() / cap.epoch_ledgers;
+        let used = match storage::get_increase_used(env, account) {
+            Some((used_epoch, used)) if used_epoch == epoch => used,
+            _ => 0,
+        };
+
+        let requested = new_score - current;
+        let applied = requested.min((i128::from(cap.max_increase) - used).max(0));
+        storage::set_increase_used(env, account, epoch, used + applied);
+        if applied < requested {
+            emit_increase_capped(env, account, &requested, &applied);
+        }
+
+        current + applied
+    }
+
+    fn require_not_frozen(env: &Env, account: &Address, new_score: i128) {
+        if new_score < get_score(env, account) && storage::get_dispute(env, account).is_some() {
+            panic_with_error!(env, Error::ScoreFrozen);
+        }
+    }
 }
 
 #[cfg(test)]
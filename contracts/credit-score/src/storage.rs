@@ -1,6 +1,17 @@
-use soroban_sdk::{Env, Map, Address, Symbol, symbol_short};
+use soroban_sdk::{contracttype, BytesN, Env, Map, Address, Symbol, symbol_short};
 
 const SCORE_KEY: Symbol = symbol_short!("score");
+const CHECKPOINT_COUNT_KEY: Symbol = symbol_short!("sc_cnt");
+const CHECKPOINT_KEY: Symbol = symbol_short!("sc_chk");
+const DISPUTE_KEY: Symbol = symbol_short!("sc_disp");
+const RESOLVED_KEY: Symbol = symbol_short!("sc_rslv");
+const ARBITER_KEY: Symbol = symbol_short!("arbiter");
+const ATTESTATION_KEY: Symbol = symbol_short!("attest");
+const ATTESTATION_NONCE_KEY: Symbol = symbol_short!("att_n");
+const CONSENT_KEY: Symbol = symbol_short!("consent");
+const INCREASE_CAP_KEY: Symbol = symbol_short!("inc_cap");
+const INCREASE_USED_KEY: Symbol = symbol_short!("inc_used");
+const REP_ORACLE_KEY: Symbol = symbol_short!("rep_orc");
 const YEAR_LEDGERS: u32 = 6_307_200;  // ~1 year in ledgers
 
 pub fn get_score(env: &Env, user: &Address) -> i128 {
@@ -15,7 +26,8 @@ pub fn set_score(env: &Env, user: &Address, score: &i128) {
     let mut scores: Map<Address, i128> = env.storage().persistent().get(&SCORE_KEY)
         .unwrap_or_else(|| Map::new(env));
 
-    let old_score = scores.get(user.clone()).unwrap_or(0i128);
+    let previous = scores.get(user.clone());
+    let old_score = previous.unwrap_or(0i128);
     scores.set(user.clone(), *score);
 
     env.storage().persistent().set(&SCORE_KEY, &scores);
@@ -25,10 +37,216 @@ pub fn set_score(env: &Env, user: &Address, score: &i128) {
 
     // Temporary storage for old score if needed
     env.storage().temporary().set(&symbol_short!("old_sc"), &old_score);
+
+    // Accounts scored before checkpoints existed held their score since ledger 0
+    if let Some(legacy) = previous {
+        if get_checkpoint_count(env, user) == 0 {
+            write_checkpoint_at(env, user, 0, legacy);
+        }
+    }
+    write_checkpoint(env, user, *score);
+}
+
+/// Score of an account from a given ledger onwards
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScoreCheckpoint {
+    pub ledger: u32,
+    pub score: i128,
+    /// Sum of score × ledgers held over all earlier checkpoints, up to `ledger`
+    pub cumulative: i128,
+}
+
+pub fn get_checkpoint_count(env: &Env, user: &Address) -> u32 {
+    env.storage().persistent().get(&(CHECKPOINT_COUNT_KEY, user.clone())).unwrap_or(0)
+}
+
+pub fn get_checkpoint(env: &Env, user: &Address, index: u32) -> Option<ScoreCheckpoint> {
+    env.storage().persistent().get(&(CHECKPOINT_KEY, user.clone(), index))
+}
+
+/// Append a checkpoint, or overwrite the latest one if it was written in the same ledger
+fn write_checkpoint(env: &Env, user: &Address, score: i128) {
+    write_checkpoint_at(env, user, env.ledger().sequence(), score);
+}
+
+fn write_checkpoint_at(env: &Env, user: &Address, ledger: u32, score: i128) {
+    let count = get_checkpoint_count(env, user);
+
+    let (index, cumulative) = match count.checked_sub(1).and_then(|last| get_checkpoint(env, user, last)) {
+        Some(latest) if latest.ledger == ledger => (count - 1, latest.cumulative),
+        Some(latest) => (count, cumulative_at(&latest, ledger)),
+        None => (count, 0),
+    };
+
+    let key = (CHECKPOINT_KEY, user.clone(), index);
+    env.storage().persistent().set(&key, &ScoreCheckpoint { ledger, score, cumulative });
+    env.storage().persistent().extend_ttl(&key, YEAR_LEDGERS, YEAR_LEDGERS);
+
+    if index == count {
+        let count_key = (CHECKPOINT_COUNT_KEY, user.clone());
+        env.storage().persistent().set(&count_key, &(count + 1));
+        env.storage().persistent().extend_ttl(&count_key, YEAR_LEDGERS, YEAR_LEDGERS);
+    }
+}
+
+/// Sum of score × ledgers held from the first checkpoint up to `ledger` (0 before it).
+/// Binary search, so the cost grows with log(checkpoints) rather than the window length.
+pub fn cumulative_score_at(env: &Env, user: &Address, ledger: u32) -> i128 {
+    // Find the last checkpoint written at or before `ledger`
+    let (mut low, mut high) = (0u32, get_checkpoint_count(env, user));
+    while low < high {
+        let mid = low + (high - low) / 2;
+        if get_checkpoint(env, user, mid).unwrap().ledger <= ledger {
+            low = mid + 1;
+        } else {
+            high = mid;
+        }
+    }
+    match low.checked_sub(1) {
+        Some(index) => cumulative_at(&get_checkpoint(env, user, index).unwrap(), ledger),
+        None => 0,
+    }
+}
+
+fn cumulative_at(checkpoint: &ScoreCheckpoint, ledger: u32) -> i128 {
+    checkpoint
+        .score
+        .saturating_mul(i128::from(ledger - checkpoint.ledger))
+        .saturating_add(checkpoint.cumulative)
 }
 
 pub fn has_score(env: &Env, user: &Address) -> bool {
     let scores: Map<Address, i128> = env.storage().persistent().get(&SCORE_KEY)
         .unwrap_or_else(|| Map::new(env));
     scores.contains_key(user.clone())
-}
\ No newline at end of file
+}
+
+pub fn get_reputation_oracle(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&REP_ORACLE_KEY)
+}
+
+pub fn set_reputation_oracle(env: &Env, oracle: &Address) {
+    env.storage().instance().set(&REP_ORACLE_KEY, oracle);
+}
+
+/// An open dispute against one score change
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScoreDispute {
+    pub checkpoint_index: u32,
+    pub reason_hash: BytesN<32>,
+    /// Score before the disputed change
+    pub previous_score: i128,
+    /// Score set by the disputed change
+    pub disputed_score: i128,
+    pub filed_at: u32,
+}
+
+pub fn get_dispute(env: &Env, user: &Address) -> Option<ScoreDispute> {
+    env.storage().persistent().get(&(DISPUTE_KEY, user.clone()))
+}
+
+pub fn set_dispute(env: &Env, user: &Address, dispute: &ScoreDispute) {
+    let key = (DISPUTE_KEY, user.clone());
+    env.storage().persistent().set(&key, dispute);
+    env.storage().persistent().extend_ttl(&key, YEAR_LEDGERS, YEAR_LEDGERS);
+}
+
+pub fn remove_dispute(env: &Env, user: &Address) {
+    env.storage().persistent().remove(&(DISPUTE_KEY, user.clone()));
+}
+
+/// Whether a dispute against this checkpoint has already been resolved
+pub fn is_dispute_resolved(env: &Env, user: &Address, checkpoint_index: u32) -> bool {
+    env.storage().persistent().has(&(RESOLVED_KEY, user.clone(), checkpoint_index))
+}
+
+pub fn set_dispute_resolved(env: &Env, user: &Address, checkpoint_index: u32) {
+    let key = (RESOLVED_KEY, user.clone(), checkpoint_index);
+    env.storage().persistent().set(&key, &true);
+    env.storage().persistent().extend_ttl(&key, YEAR_LEDGERS, YEAR_LEDGERS);
+}
+
+pub fn get_arbiter(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&ARBITER_KEY)
+}
+
+pub fn set_arbiter(env: &Env, arbiter: &Address) {
+    env.storage().instance().set(&ARBITER_KEY, arbiter);
+}
+
+/// Proof that an account's score was at least `threshold` when issued
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScoreAttestation {
+    pub account: Address,
+    pub threshold: u32,
+    pub issued_at: u32,
+    pub expires_at: u32,
+}
+
+pub fn get_attestation(env: &Env, id: &BytesN<32>) -> Option<ScoreAttestation> {
+    env.storage().persistent().get(&(ATTESTATION_KEY, id.clone()))
+}
+
+pub fn set_attestation(env: &Env, id: &BytesN<32>, attestation: &ScoreAttestation) {
+    let key = (ATTESTATION_KEY, id.clone());
+    env.storage().persistent().set(&key, attestation);
+    let ttl = attestation.expires_at - attestation.issued_at;
+    env.storage().persistent().extend_ttl(&key, ttl, ttl);
+}
+
+/// Per-account attestation counter, used to derive unique attestation ids
+pub fn next_attestation_nonce(env: &Env, user: &Address) -> u32 {
+    let key = (ATTESTATION_NONCE_KEY, user.clone());
+    let nonce: u32 = env.storage().persistent().get(&key).unwrap_or(0);
+    env.storage().persistent().set(&key, &(nonce + 1));
+    env.storage().persistent().extend_ttl(&key, YEAR_LEDGERS, YEAR_LEDGERS);
+    nonce
+}
+
+pub fn has_consent(env: &Env, user: &Address, reader: &Address) -> bool {
+    env.storage().persistent().has(&(CONSENT_KEY, user.clone(), reader.clone()))
+}
+
+pub fn set_consent(env: &Env, user: &Address, reader: &Address) {
+    let key = (CONSENT_KEY, user.clone(), reader.clone());
+    env.storage().persistent().set(&key, &true);
+    env.storage().persistent().extend_ttl(&key, YEAR_LEDGERS, YEAR_LEDGERS);
+}
+
+pub fn remove_consent(env: &Env, user: &Address, reader: &Address) {
+    env.storage().persistent().remove(&(CONSENT_KEY, user.clone(), reader.clone()));
+}
+
+/// Limit on how much a score may rise within one epoch
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IncreaseCap {
+    pub epoch_ledgers: u32,
+    pub max_increase: u32,
+}
+
+pub fn get_increase_cap(env: &Env) -> Option<IncreaseCap> {
+    env.storage().instance().get(&INCREASE_CAP_KEY)
+}
+
+pub fn set_increase_cap(env: &Env, cap: &IncreaseCap) {
+    env.storage().instance().set(&INCREASE_CAP_KEY, cap);
+}
+
+pub fn remove_increase_cap(env: &Env) {
+    env.storage().instance().remove(&INCREASE_CAP_KEY);
+}
+
+/// Increase already applied to an account in an epoch: (epoch, amount)
+pub fn get_increase_used(env: &Env, user: &Address) -> Option<(u32, i128)> {
+    env.storage().persistent().get(&(INCREASE_USED_KEY, user.clone()))
+}
+
+pub fn set_increase_used(env: &Env, user: &Address, epoch: u32, used: i128) {
+    let key = (INCREASE_USED_KEY, user.clone());
+    env.storage().persistent().set(&key, &(epoch, used));
+    env.storage().persistent().extend_ttl(&key, YEAR_LEDGERS, YEAR_LEDGERS);
+}
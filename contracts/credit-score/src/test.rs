@@ -1,5 +1,25 @@
-use crate::{CreditScoreContract, CreditScoreContractClient};
-use soroban_sdk::{testutils::Address as _, Address, BytesN, Env, String};
+use crate::{CreditScoreContract, CreditScoreContractClient, Error, MAX_ATTESTATION_TTL, MAX_REPUTATION_BONUS};
+use soroban_sdk::{
+    contract, contractimpl,
+    symbol_short,
+    testutils::{Address as _, Ledger, MockAuth, MockAuthInvoke},
+    Address, BytesN, Env, IntoVal, Map, String,
+};
+
+/// Reputation oracle returning a fixed reputation per account
+#[contract]
+pub struct MockReputationOracle;
+
+#[contractimpl]
+impl MockReputationOracle {
+    pub fn set_reputation(env: Env, account: Address, reputation: u32) {
+        env.storage().persistent().set(&account, &reputation);
+    }
+
+    pub fn get_reputation(env: Env, account: Address) -> u32 {
+        env.storage().persistent().get(&account).unwrap_or(0)
+    }
+}
 
 /// Helper function to setup the contract with an admin
 fn setup_contract(env: &Env) -> (Address, CreditScoreContractClient<'_>) {
@@ -52,14 +72,14 @@ fn test_store_and_retrieve_score() {
     let user = Address::generate(&env);
 
     // Initially user should not have a score
-    assert_eq!(client.has_score(&user), false);
+    assert!(!client.has_score(&user));
 
     // Update factors to set a score
     let boost_str = String::from_str(&env, "boost");
     client.update_factors(&user, &boost_str);
 
     // Now user should have a score
-    assert_eq!(client.has_score(&user), true);
+    assert!(client.has_score(&user));
 
     // Retrieve the score
     let score = client.get_score(&user);
@@ -145,16 +165,16 @@ fn test_has_score() {
     let user2 = Address::generate(&env);
 
     // Initially no scores
-    assert_eq!(client.has_score(&user1), false);
-    assert_eq!(client.has_score(&user2), false);
+    assert!(!client.has_score(&user1));
+    assert!(!client.has_score(&user2));
 
     // Set score for user1
     let boost_str = String::from_str(&env, "boost");
     client.update_factors(&user1, &boost_str);
 
     // Now user1 has score but user2 doesn't
-    assert_eq!(client.has_score(&user1), true);
-    assert_eq!(client.has_score(&user2), false);
+    assert!(client.has_score(&user1));
+    assert!(!client.has_score(&user2));
 }
 
 #[test]
@@ -202,10 +222,10 @@ fn test_upgrade_admin_only() {
     let env = Env::default();
     env.mock_all_auths();
 
-    let (admin, client) = setup_contract(&env);
+    let (_admin, _client) = setup_contract(&env);
 
     // Create a dummy new WASM hash (32 bytes of zeros for testing)
-    let new_wasm_hash = BytesN::from_array(&env, &[0u8; 32]);
+    let _new_wasm_hash = BytesN::from_array(&env, &[0u8; 32]);
 
     // Admin should be able to call upgrade (won't actually upgrade in test, but shouldn't panic on auth check)
     // Note: This will panic with AlreadyUpgraded since version check fails, but that's expected
@@ -298,8 +318,8 @@ fn test_authorization_required() {
     // Don't mock auths to test that authorization is required
 
     let contract_id = env.register_contract(None, CreditScoreContract);
-    let client = CreditScoreContractClient::new(&env, &contract_id);
-    let admin = Address::generate(&env);
+    let _client = CreditScoreContractClient::new(&env, &contract_id);
+    let _admin = Address::generate(&env);
 
     // Without mock_all_auths, operations requiring auth should enforce it
     // This test verifies the contract properly requires authorization
@@ -340,3 +360,484 @@ fn test_edge_case_zero_score() {
     client.update_factors(&user, &penalty_str);
     assert_eq!(client.get_score(&user), 0);
 }
+
+#[test]
+fn test_calculate_score_with_reputation_oracle() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (admin, client) = setup_contract(&env);
+    let oracle = MockReputationOracleClient::new(&env, &env.register_contract(None, MockReputationOracle));
+    let user = Address::generate(&env);
+    let newcomer = Address::generate(&env);
+
+    client.set_reputation_oracle(&admin, &oracle.address);
+    assert_eq!(client.get_reputation_oracle(), Some(oracle.address.clone()));
+
+    // 500 / 1000 reputation adds half the bonus
+    oracle.set_reputation(&user, &500);
+    assert_eq!(client.calculate_score(&user), 650);
+
+    // Full reputation adds the full bonus
+    oracle.set_reputation(&user, &1_000);
+    assert_eq!(client.calculate_score(&user), 600 + MAX_REPUTATION_BONUS);
+
+    // No reputation leaves the base score
+    assert_eq!(client.calculate_score(&newcomer), 600);
+}
+
+#[test]
+fn test_set_reputation_oracle_admin_only() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client) = setup_contract(&env);
+    let outsider = Address::generate(&env);
+    let oracle = Address::generate(&env);
+
+    assert!(client.try_set_reputation_oracle(&outsider, &oracle).is_err());
+    assert_eq!(client.get_reputation_oracle(), None);
+}
+
+#[test]
+fn test_score_changes_are_checkpointed() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client) = setup_contract(&env);
+    let user = Address::generate(&env);
+    let boost_str = String::from_str(&env, "boost");
+
+    env.ledger().with_mut(|l| l.sequence_number = 100);
+    client.update_factors(&user, &boost_str);
+    // A second change in the same ledger overwrites the checkpoint
+    client.update_factors(&user, &boost_str);
+
+    env.ledger().with_mut(|l| l.sequence_number = 150);
+    client.update_factors(&user, &String::from_str(&env, "penalty"));
+
+    assert_eq!(client.get_checkpoint_count(&user), 2);
+    let first = client.get_checkpoint(&user, &0).unwrap();
+    assert_eq!((first.ledger, first.score), (100, 100));
+    let second = client.get_checkpoint(&user, &1).unwrap();
+    assert_eq!((second.ledger, second.score), (150, 50));
+    // 100 held for 50 ledgers before the second checkpoint
+    assert_eq!((first.cumulative, second.cumulative), (0, 5_000));
+    assert_eq!(client.get_checkpoint(&user, &2), None);
+}
+
+#[test]
+fn test_time_weighted_score() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client) = setup_contract(&env);
+    let user = Address::generate(&env);
+    let boost_str = String::from_str(&env, "boost");
+
+    // 50 from ledger 100, 100 from ledger 200
+    env.ledger().with_mut(|l| l.sequence_number = 100);
+    client.update_factors(&user, &boost_str);
+    env.ledger().with_mut(|l| l.sequence_number = 200);
+    client.update_factors(&user, &boost_str);
+
+    env.ledger().with_mut(|l| l.sequence_number = 300);
+    // Window fully inside the latest score
+    assert_eq!(client.get_time_weighted_score(&user, &100), 100);
+    // Half at 50, half at 100
+    assert_eq!(client.get_time_weighted_score(&user, &200), 75);
+    // Ledgers before the first checkpoint count as 0
+    assert_eq!(client.get_time_weighted_score(&user, &400), 50);
+    // An empty window is the current score
+    assert_eq!(client.get_time_weighted_score(&user, &0), 100);
+}
+
+#[test]
+fn test_time_weighted_score_for_account_scored_before_checkpoints() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client) = setup_contract(&env);
+    let user = Address::generate(&env);
+
+    // Score written by the pre-checkpoint contract version
+    env.as_contract(&client.address, || {
+        let mut scores = Map::<Address, i128>::new(&env);
+        scores.set(user.clone(), 80);
+        env.storage().persistent().set(&symbol_short!("score"), &scores);
+    });
+
+    env.ledger().with_mut(|l| l.sequence_number = 1_000);
+    assert_eq!(client.get_checkpoint_count(&user), 0);
+    assert_eq!(client.get_time_weighted_score(&user, &500), 80);
+
+    // The first checkpointed change keeps the legacy score for the ledgers before it
+    client.update_factors(&user, &String::from_str(&env, "boost"));
+    assert_eq!(client.get_checkpoint_count(&user), 2);
+    env.ledger().with_mut(|l| l.sequence_number = 1_100);
+    assert_eq!(client.get_time_weighted_score(&user, &200), 105);
+}
+
+#[test]
+fn test_time_weighted_score_ignores_last_minute_boost() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client) = setup_contract(&env);
+    let user = Address::generate(&env);
+    let boost_str = String::from_str(&env, "boost");
+
+    env.ledger().with_mut(|l| l.sequence_number = 1_000);
+    client.update_factors(&user, &boost_str);
+
+    // Boosted four times in the current ledger
+    env.ledger().with_mut(|l| l.sequence_number = 2_000);
+    for _ in 0..4 {
+        client.update_factors(&user, &boost_str);
+    }
+
+    assert_eq!(client.get_score(&user), 250);
+    assert_eq!(client.get_time_weighted_score(&user, &1_000), 50);
+
+    // Held for 100 ledgers out of 1000
+    env.ledger().with_mut(|l| l.sequence_number = 2_100);
+    assert_eq!(client.get_time_weighted_score(&user, &1_000), 70);
+}
+
+/// Account with checkpoints 0: 100 (ledger 100) and 1: 50 (ledger 200, penalty)
+fn setup_penalized_user<'a>(env: &'a Env) -> (Address, CreditScoreContractClient<'a>, Address) {
+    let (admin, client) = setup_contract(env);
+    let user = Address::generate(env);
+    let boost_str = String::from_str(env, "boost");
+
+    env.ledger().with_mut(|l| l.sequence_number = 100);
+    client.update_factors(&user, &boost_str);
+    client.update_factors(&user, &boost_str);
+    env.ledger().with_mut(|l| l.sequence_number = 200);
+    client.update_factors(&user, &String::from_str(env, "penalty"));
+
+    (admin, client, user)
+}
+
+#[test]
+fn test_dispute_freezes_downward_adjustments() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client, user) = setup_penalized_user(&env);
+    let reason = BytesN::from_array(&env, &[1u8; 32]);
+
+    client.dispute_score_change(&user, &1, &reason);
+    let dispute = client.get_dispute(&user, &user).unwrap();
+    assert_eq!(dispute.previous_score, 100);
+    assert_eq!(dispute.disputed_score, 50);
+
+    assert_eq!(
+        client.try_update_factors(&user, &String::from_str(&env, "penalty")),
+        Err(Ok(Error::ScoreFrozen.into()))
+    );
+    // Upward adjustments still apply
+    client.update_factors(&user, &String::from_str(&env, "boost"));
+    assert_eq!(client.get_score(&user), 100);
+
+    assert_eq!(
+        client.try_dispute_score_change(&user, &0, &reason),
+        Err(Ok(Error::DisputeOpen.into()))
+    );
+}
+
+#[test]
+fn test_upheld_dispute_reverses_change() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (admin, client, user) = setup_penalized_user(&env);
+    let arbiter = Address::generate(&env);
+    client.set_arbiter(&admin, &arbiter);
+
+    client.dispute_score_change(&user, &1, &BytesN::from_array(&env, &[1u8; 32]));
+    client.resolve_dispute(&arbiter, &user, &true);
+
+    assert_eq!(client.get_score(&user), 100);
+    assert_eq!(client.get_dispute(&user, &user), None);
+
+    // Freeze is lifted
+    client.update_factors(&user, &String::from_str(&env, "penalty"));
+    assert_eq!(client.get_score(&user), 50);
+}
+
+#[test]
+fn test_rejected_dispute_keeps_score() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (admin, client, user) = setup_penalized_user(&env);
+
+    client.dispute_score_change(&user, &1, &BytesN::from_array(&env, &[1u8; 32]));
+    client.resolve_dispute(&admin, &user, &false);
+
+    assert_eq!(client.get_score(&user), 50);
+    assert_eq!(client.get_dispute(&user, &user), None);
+}
+
+#[test]
+fn test_resolved_checkpoint_cannot_be_disputed_again() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (admin, client, user) = setup_penalized_user(&env);
+    let reason = BytesN::from_array(&env, &[1u8; 32]);
+
+    // Upholding the same penalty twice would reverse it twice
+    client.dispute_score_change(&user, &1, &reason);
+    client.resolve_dispute(&admin, &user, &true);
+    assert_eq!(client.get_score(&user), 100);
+    assert_eq!(
+        client.try_dispute_score_change(&user, &1, &reason),
+        Err(Ok(Error::DisputeResolved.into()))
+    );
+
+    // A rejected dispute is final as well
+    client.dispute_score_change(&user, &0, &reason);
+    client.resolve_dispute(&admin, &user, &false);
+    assert_eq!(
+        client.try_dispute_score_change(&user, &0, &reason),
+        Err(Ok(Error::DisputeResolved.into()))
+    );
+    assert_eq!(client.get_score(&user), 100);
+}
+
+#[test]
+fn test_dispute_validation() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (admin, client, user) = setup_penalized_user(&env);
+    let outsider = Address::generate(&env);
+    let reason = BytesN::from_array(&env, &[1u8; 32]);
+
+    assert_eq!(
+        client.try_dispute_score_change(&user, &2, &reason),
+        Err(Ok(Error::CheckpointNotFound.into()))
+    );
+    assert_eq!(
+        client.try_resolve_dispute(&outsider, &user, &true),
+        Err(Ok(Error::NotArbiter.into()))
+    );
+
+    assert_eq!(
+        client.try_resolve_dispute(&admin, &user, &true),
+        Err(Ok(Error::NoOpenDispute.into()))
+    );
+
+    client.dispute_score_change(&user, &1, &reason);
+    assert_eq!(
+        client.try_resolve_dispute(&outsider, &user, &true),
+        Err(Ok(Error::NotArbiter.into()))
+    );
+}
+
+#[test]
+fn test_threshold_attestation() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client) = setup_contract(&env);
+    let user = Address::generate(&env);
+    client.update_factors(&user, &String::from_str(&env, "boost"));
+
+    env.ledger().with_mut(|l| l.sequence_number = 1_000);
+    let id = client.attest_score_threshold(&user, &40, &100);
+
+    let attestation = client.verify_attestation(&id).unwrap();
+    assert_eq!(attestation.account, user);
+    assert_eq!(attestation.threshold, 40);
+    assert_eq!(attestation.expires_at, 1_100);
+
+    // Each attestation gets its own id
+    let other = client.attest_score_threshold(&user, &50, &100);
+    assert_ne!(id, other);
+
+    env.ledger().with_mut(|l| l.sequence_number = 1_101);
+    assert_eq!(client.verify_attestation(&id), None);
+}
+
+#[test]
+fn test_threshold_attestation_validation() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client) = setup_contract(&env);
+    let user = Address::generate(&env);
+    client.update_factors(&user, &String::from_str(&env, "boost"));
+
+    assert_eq!(
+        client.try_attest_score_threshold(&user, &51, &100),
+        Err(Ok(Error::BelowThreshold.into()))
+    );
+    assert_eq!(
+        client.try_attest_score_threshold(&user, &50, &0),
+        Err(Ok(Error::InvalidTtl.into()))
+    );
+    assert_eq!(
+        client.try_attest_score_threshold(&user, &50, &(MAX_ATTESTATION_TTL + 1)),
+        Err(Ok(Error::InvalidTtl.into()))
+    );
+    assert_eq!(client.verify_attestation(&BytesN::from_array(&env, &[0u8; 32])), None);
+}
+
+#[test]
+fn test_score_consent_registry() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client) = setup_contract(&env);
+    let user = Address::generate(&env);
+    let lender = Address::generate(&env);
+    client.update_factors(&user, &String::from_str(&env, "boost"));
+
+    assert!(!client.has_consent(&user, &lender));
+    assert_eq!(
+        client.try_get_score_for(&lender, &user),
+        Err(Ok(Error::NoConsent.into()))
+    );
+
+    client.grant_consent(&user, &lender);
+    assert!(client.has_consent(&user, &lender));
+    assert_eq!(client.get_score_for(&lender, &user), 50);
+
+    client.revoke_consent(&user, &lender);
+    assert_eq!(
+        client.try_get_score_for(&lender, &user),
+        Err(Ok(Error::NoConsent.into()))
+    );
+
+    // Accounts can always read their own score
+    assert_eq!(client.get_score_for(&user, &user), 50);
+}
+
+#[test]
+fn test_time_weighted_score_for_requires_consent() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client) = setup_contract(&env);
+    let user = Address::generate(&env);
+    let lender = Address::generate(&env);
+    env.ledger().with_mut(|l| l.sequence_number = 100);
+    client.update_factors(&user, &String::from_str(&env, "boost"));
+    env.ledger().with_mut(|l| l.sequence_number = 200);
+
+    assert_eq!(
+        client.try_get_time_weighted_score_for(&lender, &user, &200),
+        Err(Ok(Error::NoConsent.into()))
+    );
+
+    client.grant_consent(&user, &lender);
+    assert_eq!(client.get_time_weighted_score_for(&lender, &user, &200), 25);
+    assert_eq!(client.get_time_weighted_score_for(&user, &user, &200), 25);
+}
+
+#[test]
+fn test_dispute_readable_by_account_consent_and_resolvers() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (admin, client, user) = setup_penalized_user(&env);
+    let arbiter = Address::generate(&env);
+    let lender = Address::generate(&env);
+    client.set_arbiter(&admin, &arbiter);
+    client.dispute_score_change(&user, &1, &BytesN::from_array(&env, &[1u8; 32]));
+
+    assert_eq!(
+        client.try_get_dispute(&lender, &user),
+        Err(Ok(Error::NoConsent.into()))
+    );
+
+    client.grant_consent(&user, &lender);
+    for reader in [&user, &lender, &admin, &arbiter] {
+        assert_eq!(client.get_dispute(reader, &user).unwrap().disputed_score, 50);
+    }
+}
+
+#[test]
+fn test_score_reads_require_account_or_consent() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, client) = setup_contract(&env);
+    let user = Address::generate(&env);
+    let lender = Address::generate(&env);
+    client.update_factors(&user, &String::from_str(&env, "boost"));
+    client.grant_consent(&user, &lender);
+
+    // Only the lender signs from here on
+    env.mock_auths(&[]);
+    assert!(client.try_get_score(&user).is_err());
+    assert!(client.try_get_checkpoint(&user, &0).is_err());
+    assert!(client.try_get_time_weighted_score(&user, &100).is_err());
+    assert!(client.try_get_dispute(&user, &user).is_err());
+
+    env.mock_auths(&[MockAuth {
+        address: &lender,
+        invoke: &MockAuthInvoke {
+            contract: &client.address,
+            fn_name: "get_score_for",
+            args: (lender.clone(), user.clone()).into_val(&env),
+            sub_invokes: &[],
+        },
+    }]);
+    assert_eq!(client.get_score_for(&lender, &user), 50);
+}
+
+#[test]
+fn test_score_increase_cap_per_epoch() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (admin, client) = setup_contract(&env);
+    let user = Address::generate(&env);
+    let boost_str = String::from_str(&env, "boost");
+
+    // At most +120 per 1000 ledgers
+    client.set_increase_cap(&admin, &1_000, &120);
+
+    env.ledger().with_mut(|l| l.sequence_number = 1_000);
+    client.update_factors(&user, &boost_str);
+    client.update_factors(&user, &boost_str);
+    // Only 20 of this boost fits in the epoch
+    client.update_factors(&user, &boost_str);
+    assert_eq!(client.get_score(&user), 120);
+    client.update_factors(&user, &boost_str);
+    assert_eq!(client.get_score(&user), 120);
+
+    // Decreases are never capped
+    client.update_factors(&user, &String::from_str(&env, "penalty"));
+    assert_eq!(client.get_score(&user), 70);
+
+    // The allowance resets in the next epoch
+    env.ledger().with_mut(|l| l.sequence_number = 2_000);
+    client.update_factors(&user, &boost_str);
+    assert_eq!(client.get_score(&user), 120);
+}
+
+#[test]
+fn test_score_increase_cap_admin_only() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (admin, client) = setup_contract(&env);
+    let outsider = Address::generate(&env);
+
+    assert!(client.try_set_increase_cap(&outsider, &1_000, &120).is_err());
+    assert_eq!(
+        client.try_set_increase_cap(&admin, &0, &120),
+        Err(Ok(Error::InvalidEpoch.into()))
+    );
+
+    client.set_increase_cap(&admin, &1_000, &120);
+    assert_eq!(client.get_increase_cap().unwrap().max_increase, 120);
+
+    client.clear_increase_cap(&admin);
+    assert_eq!(client.get_increase_cap(), None);
+}
@@ -18,6 +18,16 @@ pub enum Error {
     InvalidVersion = 3,
     MigrationFailed = 4,
     RollbackNotAvailable = 5,
+    DisputeOpen = 6,
+    CheckpointNotFound = 7,
+    NoOpenDispute = 8,
+    ScoreFrozen = 9,
+    NotArbiter = 10,
+    BelowThreshold = 11,
+    InvalidTtl = 12,
+    NoConsent = 13,
+    InvalidEpoch = 14,
+    DisputeResolved = 15,
 }
 
 #[contracttype]
@@ -180,6 +190,22 @@ pub fn get_version(env: &Env) -> u32 {
         .unwrap_or(0)
 }
 
+/// Check whether `caller` is the contract admin
+pub fn is_admin(env: &Env, caller: &Address) -> bool {
+    let admin_stored: Option<Address> = env.storage().instance().get(&UpgradeKey::Admin);
+    admin_stored == Some(caller.clone())
+}
+
+/// Require that `admin` is the contract admin and has authorized the call
+pub fn require_admin(env: &Env, admin: &Address) {
+    admin.require_auth();
+
+    let admin_stored: Address = env.storage().instance().get(&UpgradeKey::Admin).unwrap();
+    if *admin != admin_stored {
+        panic_with_error!(env, Error::NotAdmin);
+    }
+}
+
 // ========== INTERNAL MIGRATION HELPERS ==========
 
 fn execute_migrations(env: &Env, from_version: u32, to_version: u32) {
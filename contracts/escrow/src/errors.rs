@@ -0,0 +1,24 @@
+use soroban_sdk::contracterror;
+
+/// Errors returned by the escrow contract
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum EscrowError {
+    /// Contract already initialized
+    AlreadyInitialized = 1,
+    /// Caller is not the admin
+    Unauthorized = 2,
+    /// Listing price must be positive
+    InvalidPrice = 3,
+    /// Listing does not exist (never created, sold or cancelled)
+    ListingNotFound = 4,
+    /// Fee or royalty above the allowed maximum
+    FeeTooHigh = 5,
+    /// Only the seller can cancel a listing
+    NotSeller = 6,
+    /// Sellers cannot buy their own listing
+    SelfPurchase = 7,
+    /// Fee or royalty computation overflowed for this price
+    ArithmeticOverflow = 8,
+}
@@ -0,0 +1,46 @@
+use soroban_sdk::{symbol_short, Address, Env, Symbol};
+
+use crate::storage::Listing;
+
+const TOPIC_LISTED: Symbol = symbol_short!("listed");
+const TOPIC_SOLD: Symbol = symbol_short!("sold");
+const TOPIC_CANCELLED: Symbol = symbol_short!("cancel");
+const TOPIC_FEE_UPDATE: Symbol = symbol_short!("fee_upd");
+const TOPIC_ROYALTY_UPDATE: Symbol = symbol_short!("roy_upd");
+
+pub fn emit_listed(env: &Env, listing: &Listing) {
+    env.events().publish(
+        (TOPIC_LISTED, listing.id),
+        (
+            listing.seller.clone(),
+            listing.badge_contract.clone(),
+            listing.token_id,
+            listing.payment_token.clone(),
+            listing.price,
+        ),
+    );
+}
+
+pub fn emit_sold(env: &Env, listing: &Listing, buyer: &Address, fee: i128, royalty: i128) {
+    env.events().publish(
+        (TOPIC_SOLD, listing.id),
+        (buyer.clone(), listing.price, fee, royalty),
+    );
+}
+
+pub fn emit_cancelled(env: &Env, listing: &Listing) {
+    env.events()
+        .publish((TOPIC_CANCELLED, listing.id), listing.seller.clone());
+}
+
+pub fn emit_fee_updated(env: &Env, recipient: &Address, fee_bps: u32) {
+    env.events()
+        .publish((TOPIC_FEE_UPDATE,), (recipient.clone(), fee_bps));
+}
+
+pub fn emit_royalty_updated(env: &Env, badge_contract: &Address, quest_id: u32, author: &Address, royalty_bps: u32) {
+    env.events().publish(
+        (TOPIC_ROYALTY_UPDATE, badge_contract.clone()),
+        (quest_id, author.clone(), royalty_bps),
+    );
+}
@@ -0,0 +1,197 @@
+#![no_std]
+
+use soroban_sdk::{contract, contractclient, contractimpl, panic_with_error, token, Address, Env};
+
+mod errors;
+mod events;
+mod storage;
+
+pub use crate::errors::EscrowError;
+pub use crate::storage::{FeeConfig, Listing, Royalty};
+
+use crate::events::{emit_cancelled, emit_fee_updated, emit_listed, emit_royalty_updated, emit_sold};
+
+/// Basis-point denominator (10_000 = 100%)
+pub const BPS_DENOMINATOR: i128 = 10_000;
+/// Maximum marketplace fee (10%)
+pub const MAX_FEE_BPS: u32 = 1_000;
+/// Maximum royalty per quest (25%)
+pub const MAX_ROYALTY_BPS: u32 = 2_500;
+
+/// Interface the escrow expects from a badge NFT contract
+#[contractclient(name = "BadgeClient")]
+pub trait BadgeInterface {
+    /// Transfer a badge; `from` must authorize
+    fn transfer(env: Env, from: Address, to: Address, token_id: u128);
+    /// Quest the badge was issued for (used to look up the author royalty)
+    fn quest_id(env: Env, token_id: u128) -> u32;
+}
+
+#[contract]
+pub struct EscrowContract;
+
+#[contractimpl]
+impl EscrowContract {
+    /// Initialize the escrow with an admin and marketplace fee
+    pub fn initialize(env: Env, admin: Address, fee_recipient: Address, fee_bps: u32) {
+        if storage::has_admin(&env) {
+            panic_with_error!(&env, EscrowError::AlreadyInitialized);
+        }
+        admin.require_auth();
+
+        if fee_bps > MAX_FEE_BPS {
+            panic_with_error!(&env, EscrowError::FeeTooHigh);
+        }
+
+        storage::set_admin(&env, &admin);
+        storage::set_fee_config(&env, &FeeConfig { recipient: fee_recipient.clone(), fee_bps });
+        emit_fee_updated(&env, &fee_recipient, fee_bps);
+    }
+
+    /// Update the marketplace fee (admin only)
+    pub fn set_fee(env: Env, admin: Address, fee_recipient: Address, fee_bps: u32) {
+        Self::require_admin(&env, &admin);
+
+        if fee_bps > MAX_FEE_BPS {
+            panic_with_error!(&env, EscrowError::FeeTooHigh);
+        }
+
+        storage::set_fee_config(&env, &FeeConfig { recipient: fee_recipient.clone(), fee_bps });
+        emit_fee_updated(&env, &fee_recipient, fee_bps);
+    }
+
+    /// Get the marketplace fee configuration
+    pub fn get_fee_config(env: Env) -> FeeConfig {
+        storage::get_fee_config(&env)
+    }
+
+    /// Register the author royalty for a quest's badges (admin only)
+    pub fn set_royalty(env: Env, admin: Address, badge_contract: Address, quest_id: u32, author: Address, royalty_bps: u32) {
+        Self::require_admin(&env, &admin);
+
+        if royalty_bps > MAX_ROYALTY_BPS {
+            panic_with_error!(&env, EscrowError::FeeTooHigh);
+        }
+
+        if royalty_bps == 0 {
+            storage::remove_royalty(&env, &badge_contract, quest_id);
+        } else {
+            storage::set_royalty(&env, &badge_contract, quest_id, &Royalty { author: author.clone(), royalty_bps });
+        }
+        emit_royalty_updated(&env, &badge_contract, quest_id, &author, royalty_bps);
+    }
+
+    /// Get the author royalty for a quest's badges
+    pub fn get_royalty(env: Env, badge_contract: Address, quest_id: u32) -> Option<Royalty> {
+        storage::get_royalty(&env, &badge_contract, quest_id)
+    }
+
+    /// List a badge for sale; the badge is held by the escrow until sold or cancelled
+    pub fn list(env: Env, seller: Address, badge_contract: Address, token_id: u128, payment_token: Address, price: i128) -> u64 {
+        seller.require_auth();
+
+        if price <= 0 {
+            panic_with_error!(&env, EscrowError::InvalidPrice);
+        }
+
+        let badge = BadgeClient::new(&env, &badge_contract);
+        let quest_id = badge.quest_id(&token_id);
+        badge.transfer(&seller, &env.current_contract_address(), &token_id);
+
+        let listing = Listing {
+            id: storage::next_listing_id(&env),
+            seller,
+            badge_contract,
+            token_id,
+            quest_id,
+            payment_token,
+            price,
+        };
+        storage::set_listing(&env, &listing);
+        emit_listed(&env, &listing);
+
+        listing.id
+    }
+
+    /// Buy a listed badge, paying the marketplace fee and author royalty out of the price
+    pub fn buy(env: Env, buyer: Address, listing_id: u64) {
+        buyer.require_auth();
+
+        let listing = Self::listing_or_panic(&env, listing_id);
+        if buyer == listing.seller {
+            panic_with_error!(&env, EscrowError::SelfPurchase);
+        }
+
+        // Settle state before any external call
+        storage::remove_listing(&env, listing_id);
+
+        let fee_config = storage::get_fee_config(&env);
+        let fee = Self::bps_of(&env, listing.price, fee_config.fee_bps);
+        let royalty = storage::get_royalty(&env, &listing.badge_contract, listing.quest_id);
+        let royalty_amount = royalty
+            .as_ref()
+            .map(|r| Self::bps_of(&env, listing.price, r.royalty_bps))
+            .unwrap_or(0);
+        let seller_amount = listing.price - fee - royalty_amount;
+
+        let payment = token::Client::new(&env, &listing.payment_token);
+        if fee > 0 {
+            payment.transfer(&buyer, &fee_config.recipient, &fee);
+        }
+        if let Some(royalty) = royalty {
+            if royalty_amount > 0 {
+                payment.transfer(&buyer, &royalty.author, &royalty_amount);
+            }
+        }
+        payment.transfer(&buyer, &listing.seller, &seller_amount);
+
+        BadgeClient::new(&env, &listing.badge_contract)
+            .transfer(&env.current_contract_address(), &buyer, &listing.token_id);
+
+        emit_sold(&env, &listing, &buyer, fee, royalty_amount);
+    }
+
+    /// Cancel a listing and return the badge to the seller
+    pub fn cancel(env: Env, seller: Address, listing_id: u64) {
+        seller.require_auth();
+
+        let listing = Self::listing_or_panic(&env, listing_id);
+        if seller != listing.seller {
+            panic_with_error!(&env, EscrowError::NotSeller);
+        }
+
+        storage::remove_listing(&env, listing_id);
+        BadgeClient::new(&env, &listing.badge_contract)
+            .transfer(&env.current_contract_address(), &seller, &listing.token_id);
+
+        emit_cancelled(&env, &listing);
+    }
+
+    /// Get an open listing
+    pub fn get_listing(env: Env, listing_id: u64) -> Option<Listing> {
+        storage::get_listing(&env, listing_id)
+    }
+
+    /// `amount * bps / BPS_DENOMINATOR`, failing instead of wrapping on huge prices
+    fn bps_of(env: &Env, amount: i128, bps: u32) -> i128 {
+        amount
+            .checked_mul(bps as i128)
+            .unwrap_or_else(|| panic_with_error!(env, EscrowError::ArithmeticOverflow))
+            / BPS_DENOMINATOR
+    }
+
+    fn require_admin(env: &Env, admin: &Address) {
+        admin.require_auth();
+        if *admin != storage::get_admin(env) {
+            panic_with_error!(env, EscrowError::Unauthorized);
+        }
+    }
+
+    fn listing_or_panic(env: &Env, listing_id: u64) -> Listing {
+        storage::get_listing(env, listing_id)
+            .unwrap_or_else(|| panic_with_error!(env, EscrowError::ListingNotFound))
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,100 @@
+use soroban_sdk::{contracttype, Address, Env};
+
+const YEAR_LEDGERS: u32 = 6_307_200; // ~1 year in ledgers
+
+#[contracttype]
+#[derive(Clone)]
+enum DataKey {
+    Admin,
+    FeeConfig,
+    NextListingId,
+    Listing(u64),
+    Royalty(Address, u32),
+}
+
+/// Marketplace fee taken from every sale
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeConfig {
+    pub recipient: Address,
+    pub fee_bps: u32,
+}
+
+/// Royalty paid to a quest author on every resale of that quest's badges
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Royalty {
+    pub author: Address,
+    pub royalty_bps: u32,
+}
+
+/// A badge held in escrow and offered for sale
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Listing {
+    pub id: u64,
+    pub seller: Address,
+    pub badge_contract: Address,
+    pub token_id: u128,
+    pub quest_id: u32,
+    pub payment_token: Address,
+    pub price: i128,
+}
+
+pub fn has_admin(env: &Env) -> bool {
+    env.storage().instance().has(&DataKey::Admin)
+}
+
+pub fn get_admin(env: &Env) -> Address {
+    env.storage().instance().get(&DataKey::Admin).unwrap()
+}
+
+pub fn set_admin(env: &Env, admin: &Address) {
+    env.storage().instance().set(&DataKey::Admin, admin);
+}
+
+pub fn get_fee_config(env: &Env) -> FeeConfig {
+    env.storage().instance().get(&DataKey::FeeConfig).unwrap()
+}
+
+pub fn set_fee_config(env: &Env, config: &FeeConfig) {
+    env.storage().instance().set(&DataKey::FeeConfig, config);
+}
+
+pub fn next_listing_id(env: &Env) -> u64 {
+    let id: u64 = env.storage().instance().get(&DataKey::NextListingId).unwrap_or(1);
+    env.storage().instance().set(&DataKey::NextListingId, &(id + 1));
+    id
+}
+
+pub fn get_listing(env: &Env, id: u64) -> Option<Listing> {
+    env.storage().persistent().get(&DataKey::Listing(id))
+}
+
+pub fn set_listing(env: &Env, listing: &Listing) {
+    let key = DataKey::Listing(listing.id);
+    env.storage().persistent().set(&key, listing);
+    env.storage().persistent().extend_ttl(&key, YEAR_LEDGERS, YEAR_LEDGERS);
+}
+
+pub fn remove_listing(env: &Env, id: u64) {
+    env.storage().persistent().remove(&DataKey::Listing(id));
+}
+
+pub fn get_royalty(env: &Env, badge_contract: &Address, quest_id: u32) -> Option<Royalty> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Royalty(badge_contract.clone(), quest_id))
+}
+
+pub fn set_royalty(env: &Env, badge_contract: &Address, quest_id: u32, royalty: &Royalty) {
+    let key = DataKey::Royalty(badge_contract.clone(), quest_id);
+    env.storage().persistent().set(&key, royalty);
+    env.storage().persistent().extend_ttl(&key, YEAR_LEDGERS, YEAR_LEDGERS);
+}
+
+pub fn remove_royalty(env: &Env, badge_contract: &Address, quest_id: u32) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::Royalty(badge_contract.clone(), quest_id));
+}
@@ -0,0 +1,197 @@
+use crate::{EscrowContract, EscrowContractClient, EscrowError, MAX_FEE_BPS};
+use skill_tree::{SkillTreeContract, SkillTreeContractClient};
+use soroban_sdk::{testutils::Address as _, token, Address, Env};
+
+struct Setup<'a> {
+    env: Env,
+    admin: Address,
+    fee_recipient: Address,
+    seller: Address,
+    buyer: Address,
+    badge: SkillTreeContractClient<'a>,
+    token_id: u128,
+    payment: token::Client<'a>,
+    escrow: EscrowContractClient<'a>,
+}
+
+/// Escrow with a 2.5% fee, a SkillTree badge for quest 3 earned by the seller and a funded buyer
+fn setup<'a>() -> Setup<'a> {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let fee_recipient = Address::generate(&env);
+    let seller = Address::generate(&env);
+    let buyer = Address::generate(&env);
+
+    let escrow = EscrowContractClient::new(&env, &env.register(EscrowContract, ()));
+    escrow.initialize(&admin, &fee_recipient, &250);
+
+    let badge = SkillTreeContractClient::new(&env, &env.register(SkillTreeContract, ()));
+    badge.initialize(&admin);
+    let token_id = badge.mint_badge(&admin, &seller, &3);
+
+    let asset = env.register_stellar_asset_contract_v2(admin.clone());
+    token::StellarAssetClient::new(&env, &asset.address()).mint(&buyer, &100_000);
+    let payment = token::Client::new(&env, &asset.address());
+
+    Setup { env, admin, fee_recipient, seller, buyer, badge, token_id, payment, escrow }
+}
+
+#[test]
+fn test_list_takes_custody() {
+    let s = setup();
+
+    let listing_id = s.escrow.list(&s.seller, &s.badge.address, &s.token_id, &s.payment.address, &10_000);
+
+    assert_eq!(s.badge.owner_of(&s.token_id), s.escrow.address);
+    let listing = s.escrow.get_listing(&listing_id).unwrap();
+    assert_eq!(listing.seller, s.seller);
+    assert_eq!(listing.quest_id, 3);
+    assert_eq!(listing.price, 10_000);
+}
+
+#[test]
+fn test_only_badge_owner_can_list() {
+    let s = setup();
+
+    // The SkillTree contract refuses to move a badge its caller doesn't hold
+    assert!(s.escrow.try_list(&s.buyer, &s.badge.address, &s.token_id, &s.payment.address, &10_000).is_err());
+    assert_eq!(s.badge.owner_of(&s.token_id), s.seller);
+}
+
+#[test]
+fn test_buy_splits_fee_and_royalty() {
+    let s = setup();
+    let author = Address::generate(&s.env);
+    s.escrow.set_royalty(&s.admin, &s.badge.address, &3, &author, &500);
+
+    let listing_id = s.escrow.list(&s.seller, &s.badge.address, &s.token_id, &s.payment.address, &10_000);
+    s.escrow.buy(&s.buyer, &listing_id);
+
+    assert_eq!(s.badge.owner_of(&s.token_id), s.buyer);
+    assert_eq!(s.payment.balance(&s.fee_recipient), 250);
+    assert_eq!(s.payment.balance(&author), 500);
+    assert_eq!(s.payment.balance(&s.seller), 9_250);
+    assert_eq!(s.payment.balance(&s.buyer), 90_000);
+    assert_eq!(s.escrow.get_listing(&listing_id), None);
+}
+
+#[test]
+fn test_buy_without_royalty() {
+    let s = setup();
+
+    let listing_id = s.escrow.list(&s.seller, &s.badge.address, &s.token_id, &s.payment.address, &10_000);
+    s.escrow.buy(&s.buyer, &listing_id);
+
+    assert_eq!(s.payment.balance(&s.fee_recipient), 250);
+    assert_eq!(s.payment.balance(&s.seller), 9_750);
+}
+
+#[test]
+fn test_cancel_returns_badge() {
+    let s = setup();
+
+    let listing_id = s.escrow.list(&s.seller, &s.badge.address, &s.token_id, &s.payment.address, &10_000);
+    s.escrow.cancel(&s.seller, &listing_id);
+
+    assert_eq!(s.badge.owner_of(&s.token_id), s.seller);
+    assert_eq!(
+        s.escrow.try_buy(&s.buyer, &listing_id),
+        Err(Ok(EscrowError::ListingNotFound.into()))
+    );
+}
+
+#[test]
+fn test_only_seller_can_cancel() {
+    let s = setup();
+
+    let listing_id = s.escrow.list(&s.seller, &s.badge.address, &s.token_id, &s.payment.address, &10_000);
+
+    assert_eq!(
+        s.escrow.try_cancel(&s.buyer, &listing_id),
+        Err(Ok(EscrowError::NotSeller.into()))
+    );
+    assert_eq!(s.badge.owner_of(&s.token_id), s.escrow.address);
+}
+
+#[test]
+fn test_listing_cannot_be_bought_twice() {
+    let s = setup();
+
+    let listing_id = s.escrow.list(&s.seller, &s.badge.address, &s.token_id, &s.payment.address, &10_000);
+    s.escrow.buy(&s.buyer, &listing_id);
+
+    let other = Address::generate(&s.env);
+    assert_eq!(
+        s.escrow.try_buy(&other, &listing_id),
+        Err(Ok(EscrowError::ListingNotFound.into()))
+    );
+}
+
+#[test]
+fn test_invalid_listing_and_purchase() {
+    let s = setup();
+
+    assert_eq!(
+        s.escrow.try_list(&s.seller, &s.badge.address, &s.token_id, &s.payment.address, &0),
+        Err(Ok(EscrowError::InvalidPrice.into()))
+    );
+
+    let listing_id = s.escrow.list(&s.seller, &s.badge.address, &s.token_id, &s.payment.address, &10_000);
+    assert_eq!(
+        s.escrow.try_buy(&s.seller, &listing_id),
+        Err(Ok(EscrowError::SelfPurchase.into()))
+    );
+}
+
+#[test]
+fn test_buy_rejects_overflowing_fee() {
+    let s = setup();
+
+    // price * fee_bps does not fit in an i128
+    let listing_id = s.escrow.list(&s.seller, &s.badge.address, &s.token_id, &s.payment.address, &i128::MAX);
+    assert_eq!(
+        s.escrow.try_buy(&s.buyer, &listing_id),
+        Err(Ok(EscrowError::ArithmeticOverflow.into()))
+    );
+    assert!(s.escrow.get_listing(&listing_id).is_some());
+}
+
+#[test]
+fn test_fee_configuration_is_admin_only_and_capped() {
+    let s = setup();
+    let recipient = Address::generate(&s.env);
+
+    assert_eq!(
+        s.escrow.try_set_fee(&s.seller, &recipient, &100),
+        Err(Ok(EscrowError::Unauthorized.into()))
+    );
+    assert_eq!(
+        s.escrow.try_set_fee(&s.admin, &recipient, &(MAX_FEE_BPS + 1)),
+        Err(Ok(EscrowError::FeeTooHigh.into()))
+    );
+
+    s.escrow.set_fee(&s.admin, &recipient, &100);
+    let config = s.escrow.get_fee_config();
+    assert_eq!(config.recipient, recipient);
+    assert_eq!(config.fee_bps, 100);
+}
+
+#[test]
+fn test_royalty_registry() {
+    let s = setup();
+    let author = Address::generate(&s.env);
+
+    assert_eq!(
+        s.escrow.try_set_royalty(&s.admin, &s.badge.address, &3, &author, &5_000),
+        Err(Ok(EscrowError::FeeTooHigh.into()))
+    );
+
+    s.escrow.set_royalty(&s.admin, &s.badge.address, &3, &author, &500);
+    assert_eq!(s.escrow.get_royalty(&s.badge.address, &3).unwrap().royalty_bps, 500);
+
+    // A zero royalty clears the entry
+    s.escrow.set_royalty(&s.admin, &s.badge.address, &3, &author, &0);
+    assert_eq!(s.escrow.get_royalty(&s.badge.address, &3), None);
+}
@@ -9,6 +9,10 @@ const TOPIC_PATTERN_DETECTED: Symbol = symbol_short!("pattern");
 const TOPIC_BLACKLIST_UPDATE: Symbol = symbol_short!("blk_upd");
 const TOPIC_WHITELIST_UPDATE: Symbol = symbol_short!("wht_upd");
 const TOPIC_CONFIG_UPDATE: Symbol = symbol_short!("cfg_upd");
+const TOPIC_GOVERNOR_UPDATE: Symbol = symbol_short!("gov_upd");
+const TOPIC_GOVERNOR_REVOKE: Symbol = symbol_short!("gov_rvk");
+const TOPIC_GOVERNOR_REVOKE_SCHEDULED: Symbol = symbol_short!("gov_rsch");
+const TOPIC_GOVERNOR_REVOKE_CANCELLED: Symbol = symbol_short!("gov_rcnl");
 const TOPIC_ANOMALY_DETECTED: Symbol = symbol_short!("anomaly");
 const TOPIC_TRANSACTION_ANALYZED: Symbol = symbol_short!("tx_anlz");
 
@@ -185,6 +189,31 @@ pub fn emit_config_updated(
     );
 }
 
+pub fn emit_governor_updated(env: &Env, governor: &Address, updated_by: &Address) {
+    env.events().publish(
+        (TOPIC_GOVERNOR_UPDATE,),
+        (governor.clone(), updated_by.clone()),
+    );
+}
+
+pub fn emit_governor_revoked(env: &Env, governor: &Address, revoked_by: &Address) {
+    env.events().publish(
+        (TOPIC_GOVERNOR_REVOKE,),
+        (governor.clone(), revoked_by.clone()),
+    );
+}
+
+pub fn emit_governor_revocation_scheduled(env: &Env, governor: &Address, executable_at: u64) {
+    env.events().publish(
+        (TOPIC_GOVERNOR_REVOKE_SCHEDULED,),
+        (governor.clone(), executable_at),
+    );
+}
+
+pub fn emit_governor_revocation_cancelled(env: &Env, governor: &Address) {
+    env.events().publish((TOPIC_GOVERNOR_REVOKE_CANCELLED,), governor.clone());
+}
+
 pub fn emit_anomaly_detected(
     env: &Env,
     user: &Address,
@@ -9,7 +9,8 @@ mod upgrade;
 
 use crate::events::{
     create_fraud_alert, emit_anomaly_detected, emit_blacklist_updated, emit_config_updated,
-    emit_fraud_alert, emit_pattern_detected, emit_risk_score_calculated, emit_transaction_analyzed,
+    emit_fraud_alert, emit_governor_revocation_cancelled, emit_governor_revocation_scheduled,
+    emit_governor_revoked, emit_governor_updated, emit_pattern_detected, emit_risk_score_calculated, emit_transaction_analyzed,
     emit_whitelist_updated,
 };
 use crate::patterns::analyze_all_patterns;
@@ -20,6 +21,11 @@ use crate::storage::{
     remove_from_whitelist, set_config, store_transaction, FraudConfig, TransactionRecord,
 };
 
+pub use crate::storage::RiskTier;
+
+/// Seconds between scheduling a governor revocation and the admin being able to complete it
+pub const GOVERNOR_REVOKE_DELAY: u64 = 2 * 24 * 60 * 60;
+
 #[contract]
 pub struct FraudDetectContract;
 
@@ -42,6 +48,7 @@ impl FraudDetectContract {
         env.storage()
             .instance()
             .set(&soroban_sdk::symbol_short!("init"), &true);
+        storage::mark_lists_migrated(&env);
 
         // Initialize upgrade system
         upgrade::init_upgrade_system(&env);
@@ -149,6 +156,45 @@ impl FraudDetectContract {
         risk_score.total_score
     }
 
+    /// Lightweight risk gate for other contracts to call inline before moving funds.
+    /// Keeps a single velocity counter per account instead of running the full
+    /// pattern analysis, and emits no events.
+    pub fn check_and_record(env: Env, account: Address, amount: i128) -> RiskTier {
+        account.require_auth();
+
+        if amount <= 0 {
+            panic!("invalid amount");
+        }
+
+        let (blacklisted, whitelisted) = storage::list_membership(&env, &account);
+        if blacklisted {
+            return RiskTier::Blocked;
+        }
+
+        if whitelisted {
+            return RiskTier::Low;
+        }
+
+        let config = get_config(&env);
+        let current_time = env.ledger().timestamp();
+
+        let (window_start, count, volume) = match storage::get_gate_window(&env, &account) {
+            Some((start, count, volume)) if current_time < start.saturating_add(config.velocity_window) => {
+                (start, count + 1, volume.saturating_add(amount))
+            }
+            _ => (current_time, 1, amount),
+        };
+        storage::set_gate_window(&env, &account, &(window_start, count, volume));
+
+        if amount > config.max_single_amount || count > config.velocity_threshold {
+            RiskTier::High
+        } else if amount > config.max_single_amount / 2 || count > config.velocity_threshold / 2 {
+            RiskTier::Medium
+        } else {
+            RiskTier::Low
+        }
+    }
+
     pub fn get_risk_score(env: Env, user: Address) -> u32 {
         let current_time = env.ledger().timestamp();
         let config = get_config(&env);
@@ -215,7 +261,7 @@ impl FraudDetectContract {
     }
 
     pub fn update_model(env: Env, admin: Address, model_data: String) {
-        Self::require_admin(&env, &admin);
+        Self::require_config_authority(&env, &admin);
 
         let current_time = env.ledger().timestamp();
 
@@ -241,6 +287,14 @@ impl FraudDetectContract {
         emit_blacklist_updated(&env, &address, false, current_time, &admin);
     }
 
+    /// Move up to `limit` entries of the legacy single-map black/whitelists to per-address
+    /// storage (admin only). Returns the number of legacy entries left; until it reaches 0,
+    /// checks for unlisted addresses still read the legacy maps.
+    pub fn migrate_lists(env: Env, admin: Address, limit: u32) -> u32 {
+        Self::require_admin(&env, &admin);
+        storage::migrate_lists(&env, limit)
+    }
+
     pub fn add_to_whitelist(env: Env, admin: Address, address: Address) {
         Self::require_admin(&env, &admin);
         add_to_whitelist(&env, &address);
@@ -266,7 +320,7 @@ impl FraudDetectContract {
         risk_score_threshold: u32,
         anomaly_threshold: i64,
     ) {
-        Self::require_admin(&env, &admin);
+        Self::require_config_authority(&env, &admin);
 
         let config = FraudConfig {
             velocity_threshold,
@@ -299,6 +353,89 @@ impl FraudDetectContract {
         )
     }
 
+    /// Hand model and config updates over to a governance timelock.
+    /// Admin only while unset; afterwards only the current governor can replace itself, and the
+    /// admin can only revoke it (`revoke_config_governor`).
+    pub fn set_config_governor(env: Env, caller: Address, governor: Address) {
+        Self::require_config_authority(&env, &caller);
+
+        env.storage()
+            .instance()
+            .set(&soroban_sdk::symbol_short!("governor"), &governor);
+
+        emit_governor_updated(&env, &governor, &caller);
+    }
+
+    /// Emergency path, step 1: announce that model and config updates will return to the admin.
+    /// The revocation can only be completed GOVERNOR_REVOKE_DELAY later, which leaves time to
+    /// notice the `gov_rsch` event and for the governor to call `cancel_governor_revocation`.
+    pub fn schedule_governor_revocation(env: Env, admin: Address) -> u64 {
+        Self::require_admin(&env, &admin);
+
+        let governor = Self::get_config_governor(env.clone()).unwrap_or_else(|| panic!("no governor"));
+        let executable_at = env.ledger().timestamp() + GOVERNOR_REVOKE_DELAY;
+        env.storage()
+            .instance()
+            .set(&soroban_sdk::symbol_short!("gov_rvk"), &executable_at);
+
+        emit_governor_revocation_scheduled(&env, &governor, executable_at);
+        executable_at
+    }
+
+    /// Drop a scheduled revocation (current governor only)
+    pub fn cancel_governor_revocation(env: Env, governor: Address) {
+        if Self::get_config_governor(env.clone()) != Some(governor.clone()) {
+            panic!("not governor");
+        }
+        governor.require_auth();
+
+        if Self::get_governor_revocation(env.clone()).is_none() {
+            panic!("no revocation scheduled");
+        }
+        env.storage()
+            .instance()
+            .remove(&soroban_sdk::symbol_short!("gov_rvk"));
+
+        emit_governor_revocation_cancelled(&env, &governor);
+    }
+
+    /// Emergency path, step 2: return model and config updates to the admin once the
+    /// scheduled revocation's delay has passed.
+    /// The governance Timelock doesn't invoke its targets yet, so without this a governor that
+    /// can't make the call would lock the config for good.
+    pub fn revoke_config_governor(env: Env, admin: Address) {
+        Self::require_admin(&env, &admin);
+
+        let governor = Self::get_config_governor(env.clone()).unwrap_or_else(|| panic!("no governor"));
+        let executable_at = Self::get_governor_revocation(env.clone())
+            .unwrap_or_else(|| panic!("no revocation scheduled"));
+        if env.ledger().timestamp() < executable_at {
+            panic!("revocation delay not passed");
+        }
+
+        env.storage()
+            .instance()
+            .remove(&soroban_sdk::symbol_short!("governor"));
+        env.storage()
+            .instance()
+            .remove(&soroban_sdk::symbol_short!("gov_rvk"));
+
+        emit_governor_revoked(&env, &governor, &admin);
+    }
+
+    /// Timestamp from which a scheduled revocation can be completed, if one is pending
+    pub fn get_governor_revocation(env: Env) -> Option<u64> {
+        env.storage()
+            .instance()
+            .get(&soroban_sdk::symbol_short!("gov_rvk"))
+    }
+
+    pub fn get_config_governor(env: Env) -> Option<Address> {
+        env.storage()
+            .instance()
+            .get(&soroban_sdk::symbol_short!("governor"))
+    }
+
     pub fn is_blacklisted(env: Env, address: Address) -> bool {
         is_blacklisted(&env, &address)
     }
@@ -363,6 +500,19 @@ impl FraudDetectContract {
         upgrade::get_upgrade_history(&env)
     }
 
+    /// Model and config updates: the governor once set, the admin before that
+    fn require_config_authority(env: &Env, caller: &Address) {
+        match Self::get_config_governor(env.clone()) {
+            Some(governor) => {
+                if governor != *caller {
+                    panic!("not governor");
+                }
+                caller.require_auth();
+            }
+            None => Self::require_admin(env, caller),
+        }
+    }
+
     fn require_admin(env: &Env, admin: &Address) {
         let stored_admin: Address = env
             .storage()
@@ -370,13 +520,10 @@ impl FraudDetectContract {
             .get(&soroban_sdk::symbol_short!("admin"))
             .unwrap_or_else(|| panic!("admin not set"));
 
-        if stored_admin != *admin {
-            admin.require_auth();
-        }
-
         if stored_admin != *admin {
             panic!("not authorized");
         }
+        admin.require_auth();
     }
 }
 
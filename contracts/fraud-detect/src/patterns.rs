@@ -79,7 +79,7 @@ pub fn check_velocity_patterns(
 
     let transactions = get_transactions_in_window(env, user, window_start, current_time);
 
-    if transactions.len() as u32 >= velocity_threshold {
+    if transactions.len() >= velocity_threshold {
         let confidence = (transactions.len() as i64 * 100 / velocity_threshold as i64).min(200);
         let description =
             String::from_str(env, "High velocity: multiple transactions in short time");
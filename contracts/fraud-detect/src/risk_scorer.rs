@@ -50,8 +50,8 @@ pub fn calculate_velocity_score(
         return (0, risk_factors);
     }
 
-    let transaction_count = transactions.len() as u32;
-    let base_score = if transactions.len() as u32 >= velocity_threshold {
+    let transaction_count = transactions.len();
+    let base_score = if transactions.len() >= velocity_threshold {
         ((transaction_count - velocity_threshold) * 10).min(50)
     } else {
         0
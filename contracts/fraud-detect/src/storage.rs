@@ -1,9 +1,14 @@
-use soroban_sdk::{symbol_short, Address, Env, IntoVal, Map, String, Symbol, TryFromVal, Val, Vec};
+use soroban_sdk::{
+    contracttype, symbol_short, Address, Env, IntoVal, Map, String, Symbol, TryFromVal, Val, Vec,
+};
 
 const TRANSACTION_HISTORY_KEY: Symbol = symbol_short!("tx_hist");
 const BLACKLIST_KEY: Symbol = symbol_short!("blacklist");
 const WHITELIST_KEY: Symbol = symbol_short!("whitelist");
 const CONFIG_KEY: Symbol = symbol_short!("config");
+const GATE_KEY: Symbol = symbol_short!("gate");
+const LISTS_MIGRATED_KEY: Symbol = symbol_short!("lists_v2");
+const ACL_KEY: Symbol = symbol_short!("acl");
 const YEAR_LEDGERS: u32 = 6_307_200;
 
 #[derive(Clone, Debug)]
@@ -92,6 +97,28 @@ impl Default for FraudConfig {
     }
 }
 
+/// Result of the inline risk gate
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RiskTier {
+    Low,
+    Medium,
+    High,
+    Blocked,
+}
+
+/// Compact velocity counter used by the risk gate: (window start, count, volume)
+pub fn get_gate_window(env: &Env, user: &Address) -> Option<(u64, u32, i128)> {
+    env.storage().persistent().get(&(GATE_KEY, user))
+}
+
+pub fn set_gate_window(env: &Env, user: &Address, window: &(u64, u32, i128)) {
+    env.storage().persistent().set(&(GATE_KEY, user), window);
+    env.storage()
+        .persistent()
+        .extend_ttl(&(GATE_KEY, user), YEAR_LEDGERS, YEAR_LEDGERS);
+}
+
 pub fn store_transaction(env: &Env, user: &Address, record: &TransactionRecord) {
     let mut user_history: Vec<TransactionRecord> = get_transaction_history(env, user);
 
@@ -119,66 +146,134 @@ pub fn get_transaction_history(env: &Env, user: &Address) -> Vec<TransactionReco
         .unwrap_or_else(|| Vec::new(env))
 }
 
+// List membership is stored per address: `(acl, address) -> flags` (BLACKLISTED | WHITELISTED),
+// so checking an address is one read however long the lists are. Earlier versions kept each
+// list as a single `Map<Address, u64>` under `blacklist` / `whitelist`; those entries are still
+// honoured until `migrate_lists` has moved them all and set the (instance) `lists_v2` flag.
+// Fresh deployments set it on init.
+
+const BLACKLISTED: u32 = 1;
+const WHITELISTED: u32 = 2;
+
 pub fn add_to_blacklist(env: &Env, address: &Address) {
-    let mut blacklist: Map<Address, u64> = get_blacklist(env);
-    let current_ledger = env.ledger().sequence();
-    blacklist.set(address.clone(), current_ledger as u64);
-    env.storage().persistent().set(&BLACKLIST_KEY, &blacklist);
-    env.storage()
-        .persistent()
-        .extend_ttl(&BLACKLIST_KEY, YEAR_LEDGERS, YEAR_LEDGERS);
+    list_add(env, address, BLACKLISTED);
 }
 
 pub fn remove_from_blacklist(env: &Env, address: &Address) {
-    let mut blacklist: Map<Address, u64> = get_blacklist(env);
-    blacklist.remove(address.clone());
-    env.storage().persistent().set(&BLACKLIST_KEY, &blacklist);
-    env.storage()
-        .persistent()
-        .extend_ttl(&BLACKLIST_KEY, YEAR_LEDGERS, YEAR_LEDGERS);
-}
-
-pub fn get_blacklist(env: &Env) -> Map<Address, u64> {
-    env.storage()
-        .persistent()
-        .get(&BLACKLIST_KEY)
-        .unwrap_or_else(|| Map::new(env))
+    list_remove(env, address, BLACKLISTED);
 }
 
 pub fn is_blacklisted(env: &Env, address: &Address) -> bool {
-    let blacklist = get_blacklist(env);
-    blacklist.contains_key(address.clone())
+    list_flags(env, address) & BLACKLISTED != 0
 }
 
 pub fn add_to_whitelist(env: &Env, address: &Address) {
-    let mut whitelist: Map<Address, u64> = get_whitelist(env);
-    let current_ledger = env.ledger().sequence();
-    whitelist.set(address.clone(), current_ledger as u64);
-    env.storage().persistent().set(&WHITELIST_KEY, &whitelist);
-    env.storage()
-        .persistent()
-        .extend_ttl(&WHITELIST_KEY, YEAR_LEDGERS, YEAR_LEDGERS);
+    list_add(env, address, WHITELISTED);
 }
 
 pub fn remove_from_whitelist(env: &Env, address: &Address) {
-    let mut whitelist: Map<Address, u64> = get_whitelist(env);
-    whitelist.remove(address.clone());
-    env.storage().persistent().set(&WHITELIST_KEY, &whitelist);
-    env.storage()
-        .persistent()
-        .extend_ttl(&WHITELIST_KEY, YEAR_LEDGERS, YEAR_LEDGERS);
+    list_remove(env, address, WHITELISTED);
 }
 
-pub fn get_whitelist(env: &Env) -> Map<Address, u64> {
-    env.storage()
+pub fn is_whitelisted(env: &Env, address: &Address) -> bool {
+    list_flags(env, address) & WHITELISTED != 0
+}
+
+/// (blacklisted, whitelisted) for an address, in a single read once migrated
+pub fn list_membership(env: &Env, address: &Address) -> (bool, bool) {
+    let flags = list_flags(env, address);
+    (flags & BLACKLISTED != 0, flags & WHITELISTED != 0)
+}
+
+/// Move up to `limit` legacy map entries of both lists to per-address flags.
+/// Returns the number of legacy entries left.
+pub fn migrate_lists(env: &Env, limit: u32) -> u32 {
+    let (moved, blacklist_left) = migrate_list(env, BLACKLIST_KEY, BLACKLISTED, limit);
+    let (_, whitelist_left) = migrate_list(env, WHITELIST_KEY, WHITELISTED, limit - moved);
+    let remaining = blacklist_left + whitelist_left;
+    if remaining == 0 {
+        mark_lists_migrated(env);
+    }
+    remaining
+}
+
+/// Record that no legacy list maps are left to consult
+pub fn mark_lists_migrated(env: &Env) {
+    env.storage().instance().set(&LISTS_MIGRATED_KEY, &true);
+}
+
+fn list_flags(env: &Env, address: &Address) -> u32 {
+    let mut flags: u32 = env
+        .storage()
         .persistent()
-        .get(&WHITELIST_KEY)
-        .unwrap_or_else(|| Map::new(env))
+        .get(&(ACL_KEY, address.clone()))
+        .unwrap_or(0);
+    if !env.storage().instance().has(&LISTS_MIGRATED_KEY) {
+        for (list, flag) in [(BLACKLIST_KEY, BLACKLISTED), (WHITELIST_KEY, WHITELISTED)] {
+            if get_legacy_list(env, &list).is_some_and(|legacy| legacy.contains_key(address.clone())) {
+                flags |= flag;
+            }
+        }
+    }
+    flags
 }
 
-pub fn is_whitelisted(env: &Env, address: &Address) -> bool {
-    let whitelist = get_whitelist(env);
-    whitelist.contains_key(address.clone())
+fn set_list_flags(env: &Env, address: &Address, flags: u32) {
+    let key = (ACL_KEY, address.clone());
+    if flags == 0 {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, &flags);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, YEAR_LEDGERS, YEAR_LEDGERS);
+    }
+}
+
+fn list_add(env: &Env, address: &Address, flag: u32) {
+    set_list_flags(env, address, list_flags(env, address) | flag);
+}
+
+fn list_remove(env: &Env, address: &Address, flag: u32) {
+    let flags = list_flags(env, address) & !flag;
+    let list = if flag == BLACKLISTED { BLACKLIST_KEY } else { WHITELIST_KEY };
+    if let Some(mut legacy) = get_legacy_list(env, &list) {
+        legacy.remove(address.clone());
+        set_legacy_list(env, &list, &legacy);
+    }
+    set_list_flags(env, address, flags);
+}
+
+/// Returns (entries moved, entries left)
+fn migrate_list(env: &Env, list: Symbol, flag: u32, limit: u32) -> (u32, u32) {
+    let mut legacy = match get_legacy_list(env, &list) {
+        Some(legacy) => legacy,
+        None => return (0, 0),
+    };
+    let batch = legacy.keys().slice(0..limit.min(legacy.len()));
+    for address in batch.iter() {
+        let stored: u32 = env
+            .storage()
+            .persistent()
+            .get(&(ACL_KEY, address.clone()))
+            .unwrap_or(0);
+        set_list_flags(env, &address, stored | flag);
+        legacy.remove(address);
+    }
+    set_legacy_list(env, &list, &legacy);
+    (batch.len(), legacy.len())
+}
+
+fn get_legacy_list(env: &Env, list: &Symbol) -> Option<Map<Address, u64>> {
+    env.storage().persistent().get(list)
+}
+
+fn set_legacy_list(env: &Env, list: &Symbol, legacy: &Map<Address, u64>) {
+    if legacy.is_empty() {
+        env.storage().persistent().remove(list);
+    } else {
+        env.storage().persistent().set(list, legacy);
+    }
 }
 
 pub fn set_config(env: &Env, config: &FraudConfig) {
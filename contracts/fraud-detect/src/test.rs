@@ -1,5 +1,9 @@
-use crate::{FraudDetectContract, FraudDetectContractClient};
-use soroban_sdk::{testutils::Address as _, Address, Env, String};
+use crate::{FraudDetectContract, FraudDetectContractClient, RiskTier, GOVERNOR_REVOKE_DELAY};
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Ledger},
+    Address, Env, Map, String,
+};
 
 #[test]
 fn test_initialization() {
@@ -192,3 +196,298 @@ fn test_double_initialization_prevention() {
     // Second initialization should fail
     client.initialize(&admin);
 }
+
+#[test]
+fn test_config_governed_by_timelock() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, FraudDetectContract);
+    let client = FraudDetectContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let timelock = Address::generate(&env);
+
+    client.initialize(&admin);
+    client.set_config_governor(&admin, &timelock);
+    assert_eq!(client.get_config_governor(), Some(timelock.clone()));
+
+    // The timelock can update the config
+    client.update_config(&timelock, &20, &7200, &20000i128, &80, &90);
+    assert_eq!(client.get_config().0, 20);
+
+    // The admin no longer can
+    assert!(client
+        .try_update_config(&admin, &30, &7200, &20000i128, &80, &90)
+        .is_err());
+    assert!(client
+        .try_update_model(&admin, &String::from_str(&env, "v2"))
+        .is_err());
+    assert!(client.try_set_config_governor(&admin, &admin).is_err());
+    assert_eq!(client.get_config().0, 20);
+
+    // Only the governor can hand over to a new timelock
+    let new_timelock = Address::generate(&env);
+    client.set_config_governor(&timelock, &new_timelock);
+    assert_eq!(client.get_config_governor(), Some(new_timelock));
+}
+
+#[test]
+fn test_admin_can_revoke_governor() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, FraudDetectContract);
+    let client = FraudDetectContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let timelock = Address::generate(&env);
+
+    client.initialize(&admin);
+    client.set_config_governor(&admin, &timelock);
+
+    // Only the admin can take the config back, and only after the delay
+    assert!(client.try_schedule_governor_revocation(&timelock).is_err());
+    assert!(client.try_revoke_config_governor(&admin).is_err());
+    env.ledger().with_mut(|l| l.timestamp = 1_000);
+    assert_eq!(client.schedule_governor_revocation(&admin), 1_000 + GOVERNOR_REVOKE_DELAY);
+    assert!(client.try_revoke_config_governor(&admin).is_err());
+
+    env.ledger().with_mut(|l| l.timestamp = 1_000 + GOVERNOR_REVOKE_DELAY);
+    assert!(client.try_revoke_config_governor(&timelock).is_err());
+    client.revoke_config_governor(&admin);
+    assert_eq!(client.get_config_governor(), None);
+    assert_eq!(client.get_governor_revocation(), None);
+
+    client.update_config(&admin, &30, &7200, &20000i128, &80, &90);
+    assert_eq!(client.get_config().0, 30);
+    assert!(client.try_revoke_config_governor(&admin).is_err());
+}
+
+#[test]
+fn test_governor_can_cancel_revocation() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, FraudDetectContract);
+    let client = FraudDetectContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let timelock = Address::generate(&env);
+
+    client.initialize(&admin);
+    client.set_config_governor(&admin, &timelock);
+    client.schedule_governor_revocation(&admin);
+
+    assert!(client.try_cancel_governor_revocation(&admin).is_err());
+    client.cancel_governor_revocation(&timelock);
+    assert_eq!(client.get_governor_revocation(), None);
+
+    env.ledger().with_mut(|l| l.timestamp += GOVERNOR_REVOKE_DELAY);
+    assert!(client.try_revoke_config_governor(&admin).is_err());
+    assert_eq!(client.get_config_governor(), Some(timelock));
+}
+
+#[test]
+fn test_admin_actions_require_admin_signature() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, FraudDetectContract);
+    let client = FraudDetectContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let attacker = Address::generate(&env);
+    client.initialize(&admin);
+
+    // Naming the admin without its signature is not enough
+    env.mock_auths(&[]);
+    assert!(client.try_set_config_governor(&admin, &attacker).is_err());
+    assert!(client.try_update_config(&admin, &1, &1, &1i128, &1, &1).is_err());
+    assert!(client.try_add_to_blacklist(&admin, &attacker).is_err());
+    assert_eq!(client.get_config_governor(), None);
+}
+
+#[test]
+#[should_panic(expected = "not governor")]
+fn test_governed_config_rejects_admin() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, FraudDetectContract);
+    let client = FraudDetectContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let timelock = Address::generate(&env);
+
+    client.initialize(&admin);
+    client.set_config_governor(&admin, &timelock);
+
+    client.update_config(&admin, &30, &7200, &20000i128, &80, &90);
+}
+
+#[test]
+fn test_check_and_record_tiers() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, FraudDetectContract);
+    let client = FraudDetectContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+
+    client.initialize(&admin);
+
+    // Default config: max_single_amount 10000, velocity_threshold 10 per 3600s
+    assert_eq!(client.check_and_record(&user, &1000i128), RiskTier::Low);
+    assert_eq!(client.check_and_record(&user, &6000i128), RiskTier::Medium);
+    assert_eq!(client.check_and_record(&user, &50000i128), RiskTier::High);
+
+    client.add_to_blacklist(&admin, &user);
+    assert_eq!(client.check_and_record(&user, &1i128), RiskTier::Blocked);
+
+    client.remove_from_blacklist(&admin, &user);
+    client.add_to_whitelist(&admin, &user);
+    assert_eq!(client.check_and_record(&user, &50000i128), RiskTier::Low);
+}
+
+#[test]
+fn test_check_and_record_velocity_window() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, FraudDetectContract);
+    let client = FraudDetectContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+
+    client.initialize(&admin);
+    env.ledger().with_mut(|l| l.timestamp = 10_000);
+
+    let mut tiers = soroban_sdk::Vec::new(&env);
+    for _ in 0..11 {
+        tiers.push_back(client.check_and_record(&user, &100i128));
+    }
+    assert_eq!(tiers.get(4).unwrap(), RiskTier::Low);
+    assert_eq!(tiers.get(5).unwrap(), RiskTier::Medium);
+    assert_eq!(tiers.get(10).unwrap(), RiskTier::High);
+
+    // A new window resets the counter
+    env.ledger().with_mut(|l| l.timestamp = 10_000 + 3600);
+    assert_eq!(client.check_and_record(&user, &100i128), RiskTier::Low);
+}
+
+#[test]
+fn test_check_and_record_is_cheaper_than_full_analysis() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, FraudDetectContract);
+    let client = FraudDetectContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let from_addr = Address::generate(&env);
+    let to_addr = Address::generate(&env);
+
+    client.initialize(&admin);
+
+    env.budget().reset_default();
+    client.check_and_record(&user, &1000i128);
+    let gate_cost = env.budget().cpu_instruction_cost();
+
+    env.budget().reset_default();
+    client.analyze_transaction(
+        &user,
+        &from_addr,
+        &to_addr,
+        &1000i128,
+        &String::from_str(&env, "transfer"),
+    );
+    let analysis_cost = env.budget().cpu_instruction_cost();
+
+    assert!(gate_cost * 2 < analysis_cost);
+    assert!(gate_cost * 2 < analysis_cost);
+}
+
+#[test]
+fn test_list_lookup_cost_does_not_grow_with_lists() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.budget().reset_unlimited();
+
+    let contract_id = env.register_contract(None, FraudDetectContract);
+    let client = FraudDetectContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    client.initialize(&admin);
+
+    let lookup_cost = || {
+        env.as_contract(&contract_id, || {
+            env.budget().reset_default();
+            assert!(!crate::storage::is_blacklisted(&env, &user));
+            assert!(!crate::storage::is_whitelisted(&env, &user));
+            env.budget().cpu_instruction_cost()
+        })
+    };
+
+    let empty_cost = lookup_cost();
+    env.budget().reset_unlimited();
+    for _ in 0..100 {
+        client.add_to_blacklist(&admin, &Address::generate(&env));
+        client.add_to_whitelist(&admin, &Address::generate(&env));
+    }
+    let listed_cost = lookup_cost();
+
+    // A single map of every listed address made this grow ~20x with 100 entries per list
+    assert!(listed_cost < empty_cost * 2);
+}
+
+#[test]
+fn test_migrate_legacy_lists() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, FraudDetectContract);
+    let client = FraudDetectContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let blocked = Address::generate(&env);
+    let trusted = Address::generate(&env);
+    let removed = Address::generate(&env);
+    client.initialize(&admin);
+
+    // Lists written by the single-map storage layout, before the migration flag existed
+    env.as_contract(&contract_id, || {
+        env.storage().instance().remove(&symbol_short!("lists_v2"));
+        let mut blacklist = Map::<Address, u64>::new(&env);
+        blacklist.set(blocked.clone(), 1);
+        blacklist.set(removed.clone(), 1);
+        env.storage().persistent().set(&symbol_short!("blacklist"), &blacklist);
+        let mut whitelist = Map::<Address, u64>::new(&env);
+        whitelist.set(trusted.clone(), 1);
+        env.storage().persistent().set(&symbol_short!("whitelist"), &whitelist);
+    });
+
+    // Legacy entries are honoured and can be removed before migration
+    assert!(client.is_blacklisted(&blocked));
+    assert!(client.is_whitelisted(&trusted));
+    client.remove_from_blacklist(&admin, &removed);
+    assert!(!client.is_blacklisted(&removed));
+
+    assert!(client.try_migrate_lists(&blocked, &10).is_err());
+    assert_eq!(client.migrate_lists(&admin, &1), 1);
+    assert_eq!(client.migrate_lists(&admin, &10), 0);
+
+    assert!(client.is_blacklisted(&blocked));
+    assert!(client.is_whitelisted(&trusted));
+    assert!(!client.is_blacklisted(&removed));
+    assert_eq!(client.check_and_record(&blocked, &1i128), RiskTier::Blocked);
+    env.as_contract(&contract_id, || {
+        assert!(!env.storage().persistent().has(&symbol_short!("blacklist")));
+        assert!(!env.storage().persistent().has(&symbol_short!("whitelist")));
+    });
+}
@@ -1,7 +1,22 @@
 #![allow(unused)]
-use crate::types::{Checkpoint, GovernanceError};
+use crate::types::{Checkpoint, GovernanceError, VoteAdjustment};
 use soroban_sdk::{contract, contractimpl, panic_with_error, symbol_short, Address, Env, String, Vec};
 
+/// Maximum number of entries kept in the top-delegates leaderboard
+pub const TOP_DELEGATES_CAP: u32 = 50;
+
+/// Smallest `retain_ledgers` accepted by `compress_checkpoints` (~14 days at 5s ledgers)
+/// @notice ProposalManager rejects configs whose voting delay plus voting period exceed this, so
+/// compression can never move the horizon past the snapshot block of a live proposal
+pub const MIN_RETAIN_LEDGERS: u64 = 241_920;
+
+/// Maximum number of checkpoints removed by one `compress_checkpoints` call
+pub const COMPRESS_BATCH_LIMIT: u32 = 20;
+
+/// Maximum number of unsettled time-bound delegations a delegatee can hold
+/// @notice Every vote read walks this list, so it must stay small
+pub const MAX_TIMED_DELEGATORS: u32 = 50;
+
 /// Governance Token Contract with Compound-style checkpoints and delegation
 #[contract]
 pub struct GovernanceToken;
@@ -57,6 +72,10 @@ impl GovernanceToken {
             return;
         }
 
+        // Expired delegations must be settled before the balances they cover change
+        Self::_settle_expired(&env, &from);
+        Self::_settle_expired(&env, &to);
+
         let from_balance = Self::_get_balance(&env, &from);
         if from_balance < amount {
             panic_with_error!(&env, GovernanceError::InsufficientBalance);
@@ -115,6 +134,9 @@ impl GovernanceToken {
         );
 
         // Perform transfer
+        Self::_settle_expired(&env, &from);
+        Self::_settle_expired(&env, &to);
+
         let from_balance = Self::_get_balance(&env, &from);
         if from_balance < amount {
             panic_with_error!(&env, GovernanceError::InsufficientBalance);
@@ -137,36 +159,87 @@ impl GovernanceToken {
     /// @notice Delegation does not transfer tokens, only voting power
     pub fn delegate(env: Env, delegator: Address, delegatee: Address) {
         delegator.require_auth();
-        
-        let current_delegate = Self::_delegates(&env, &delegator);
-        let delegator_balance = Self::_get_balance(&env, &delegator);
-        
-        // Update delegate mapping
-        env.storage().persistent().set(&(symbol_short!("DELEGAT"), delegator.clone()), &delegatee);
-        
-        // Emit DelegateChanged event
-        env.events().publish(
-            (symbol_short!("DelChg"), delegator.clone()),
-            (current_delegate.clone(), delegatee.clone())
-        );
-        
-        // Move voting power
-        Self::_move_delegates(&env, &current_delegate, &delegatee, delegator_balance);
+        Self::_delegate(&env, &delegator, &delegatee, None);
+    }
+
+    /// Delegate voting power until a given ledger
+    /// @notice From `expiry_ledger` on, the power counts for the delegator again. Reads apply the
+    /// expiry straight away; settling the delegation (on the delegator's next transfer or
+    /// delegation, or via `settle_delegation`) records it against both sides' checkpoints.
+    /// @param delegator: The account delegating its voting power
+    /// @param delegatee: The account receiving the voting power
+    /// @param expiry_ledger: First ledger at which the delegation no longer applies
+    pub fn delegate_with_expiry(env: Env, delegator: Address, delegatee: Address, expiry_ledger: u64) {
+        delegator.require_auth();
+
+        if expiry_ledger <= env.ledger().sequence() as u64 {
+            panic_with_error!(&env, GovernanceError::InvalidExpiry);
+        }
+        // Empty delegations would only pad the delegatee's list
+        if Self::_get_balance(&env, &delegator) == 0 {
+            panic_with_error!(&env, GovernanceError::InsufficientBalance);
+        }
+        Self::_reserve_timed_slot(&env, &delegator, &delegatee);
+
+        Self::_delegate(&env, &delegator, &delegatee, Some(expiry_ledger));
+    }
+
+    /// Settle an expired time-bound delegation
+    /// @notice Permissionless. Returns false if the account has no expired delegation.
+    pub fn settle_delegation(env: Env, delegator: Address) -> bool {
+        Self::_settle_expired(&env, &delegator)
+    }
+
+    /// Get the expiry ledger of an account's time-bound delegation, if any
+    /// @notice Still returned after expiry until the delegation is settled
+    pub fn delegation_expiry(env: Env, account: Address) -> Option<u64> {
+        Self::_delegation_expiry(&env, &account)
     }
 
     /// Get the current delegate for an account
+    /// @notice An expired time-bound delegation resolves to the account itself
     pub fn delegates(env: Env, account: Address) -> Address {
+        if Self::_is_expired(&env, &account, env.ledger().sequence() as u64) {
+            return account;
+        }
         Self::_delegates(&env, &account)
     }
 
+    /// Get the accounts that explicitly delegate to a delegatee
+    /// @notice Order is not stable across re-delegations (entries are swap-removed)
+    /// @param delegatee: The delegate to query
+    /// @param offset: Index of the first delegator to return
+    /// @param limit: Maximum number of delegators to return
+    pub fn get_delegators(env: Env, delegatee: Address, offset: u32, limit: u32) -> Vec<Address> {
+        let count = Self::_delegator_count(&env, &delegatee);
+        let end = offset.saturating_add(limit).min(count);
+
+        let mut delegators = Vec::new(&env);
+        for i in offset..end {
+            delegators.push_back(Self::_get_delegator(&env, &delegatee, i));
+        }
+        delegators
+    }
+
+    /// Get the number of accounts that explicitly delegate to a delegatee
+    pub fn delegator_count(env: Env, delegatee: Address) -> u32 {
+        Self::_delegator_count(&env, &delegatee)
+    }
+
+    /// Get the delegates with the most voting power, highest first
+    /// @notice Ranking covers accounts tracked in the leaderboard (at most TOP_DELEGATES_CAP).
+    /// An account enters the leaderboard the next time its voting power changes.
+    /// @param limit: Maximum number of delegates to return
+    /// @return Vector of (delegate, current votes)
+    pub fn top_delegates(env: Env, limit: u32) -> Vec<(Address, u128)> {
+        let leaderboard = Self::_top_delegates(&env);
+        let end = limit.min(leaderboard.len());
+        leaderboard.slice(0..end)
+    }
+
     /// Get current votes (voting power) for an account
     pub fn get_current_votes(env: Env, account: Address) -> u128 {
-        let num_checkpoints = Self::_num_checkpoints(&env, &account);
-        if num_checkpoints > 0 {
-            Self::_get_checkpoint(&env, &account, num_checkpoints - 1).votes
-        } else {
-            0
-        }
+        Self::_votes_at(&env, &account, env.ledger().sequence() as u64)
     }
 
     /// Get prior votes (historical voting power) at a specific block
@@ -179,40 +252,90 @@ impl GovernanceToken {
             panic_with_error!(&env, GovernanceError::InvalidCheckpoint);
         }
 
-        let num_checkpoints = Self::_num_checkpoints(&env, &account);
-        if num_checkpoints == 0 {
-            return 0;
+        // History older than the compression horizon has been merged away
+        if block_number < Self::_checkpoint_horizon(&env, &account) {
+            panic_with_error!(&env, GovernanceError::InvalidCheckpoint);
+        }
+
+        Self::_votes_at(&env, &account, block_number)
+    }
+
+    /// Get the number of checkpoints stored for an account
+    pub fn num_checkpoints(env: Env, account: Address) -> u32 {
+        Self::_num_checkpoints(&env, &account)
+    }
+
+    /// Get the oldest block for which `get_prior_votes` can still answer for an account
+    /// @notice Returns 0 if the account's checkpoints have never been compressed
+    pub fn checkpoint_horizon(env: Env, account: Address) -> u64 {
+        Self::_checkpoint_horizon(&env, &account)
+    }
+
+    /// Compress an account's checkpoint history
+    /// @notice Maintenance entrypoint (admin only). Checkpoints older than `retain_ledgers` are
+    /// merged into a single entry holding the voting power in force at the cutoff, so
+    /// `get_prior_votes` stays exact for the retained range. At most COMPRESS_BATCH_LIMIT entries
+    /// are removed per call; call again until it returns 0 to compress a long history.
+    /// @param admin: The token admin
+    /// @param account: The account whose checkpoints are compressed
+    /// @param retain_ledgers: Number of most recent ledgers kept at full resolution (>= MIN_RETAIN_LEDGERS)
+    /// @return removed: Number of checkpoint entries removed from storage
+    pub fn compress_checkpoints(env: Env, admin: Address, account: Address, retain_ledgers: u64) -> u32 {
+        admin.require_auth();
+
+        let stored_admin = Self::_admin(&env);
+        if admin != stored_admin {
+            panic_with_error!(&env, GovernanceError::Unauthorized);
         }
 
-        // Binary search through checkpoints
-        // First check most recent checkpoint
-        let most_recent = Self::_get_checkpoint(&env, &account, num_checkpoints - 1);
-        if most_recent.from_block <= block_number {
-            return most_recent.votes;
+        // A shorter window could drop history that an active proposal's snapshot still needs
+        if retain_ledgers < MIN_RETAIN_LEDGERS {
+            panic_with_error!(&env, GovernanceError::InvalidCheckpoint);
         }
 
-        // Check if before first checkpoint
-        let first = Self::_get_checkpoint(&env, &account, 0);
-        if first.from_block > block_number {
+        let current_block = env.ledger().sequence() as u64;
+        let cutoff = current_block.saturating_sub(retain_ledgers);
+        let num_checkpoints = Self::_num_checkpoints(&env, &account);
+        if num_checkpoints == 0 || Self::_get_checkpoint(&env, &account, 0).from_block > cutoff {
             return 0;
         }
 
-        // Binary search
+        // Binary search for the newest checkpoint at or before the cutoff - it becomes the merged entry
         let mut lower = 0u32;
         let mut upper = num_checkpoints - 1;
         while upper > lower {
             let center = upper - (upper - lower) / 2;
-            let checkpoint = Self::_get_checkpoint(&env, &account, center);
-            if checkpoint.from_block == block_number {
-                return checkpoint.votes;
-            } else if checkpoint.from_block < block_number {
+            if Self::_get_checkpoint(&env, &account, center).from_block <= cutoff {
                 lower = center;
             } else {
                 upper = center - 1;
             }
         }
-        
-        Self::_get_checkpoint(&env, &account, lower).votes
+
+        let removed = lower.min(COMPRESS_BATCH_LIMIT);
+        if removed == 0 {
+            return 0;
+        }
+
+        // Drop the oldest entries and move the base index instead of shifting the retained ones
+        let base = Self::_checkpoint_base(&env, &account);
+        for i in 0..removed {
+            env.storage().persistent().remove(&(symbol_short!("CHKPT"), account.clone(), base + i));
+            env.storage().persistent().remove(&(symbol_short!("CHKADJ"), account.clone(), base + i));
+        }
+        env.storage().persistent().set(&(symbol_short!("CHKBASE"), account.clone()), &(base + removed));
+        env.storage().persistent().set(&(symbol_short!("NUMCHK"), account.clone()), &(num_checkpoints - removed));
+
+        let horizon = Self::_get_checkpoint(&env, &account, 0).from_block;
+        env.storage().persistent().set(&(symbol_short!("CHKHZN"), account.clone()), &horizon);
+
+        // Emit CheckpointsCompressed event
+        env.events().publish(
+            (symbol_short!("ChkCmpct"), account),
+            (removed, horizon)
+        );
+
+        removed
     }
 
     /// Get total token supply
@@ -249,6 +372,13 @@ impl GovernanceToken {
 
     // ========== INTERNAL HELPER FUNCTIONS ==========
 
+    fn _admin(env: &Env) -> Address {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("ADMIN"))
+            .unwrap()
+    }
+
     fn _get_balance(env: &Env, account: &Address) -> u128 {
         env.storage()
             .persistent()
@@ -263,6 +393,146 @@ impl GovernanceToken {
             .unwrap_or(account.clone())
     }
 
+    fn _delegation_expiry(env: &Env, account: &Address) -> Option<u64> {
+        env.storage()
+            .persistent()
+            .get(&(symbol_short!("DLGEXP"), account.clone()))
+    }
+
+    fn _is_expired(env: &Env, account: &Address, block_number: u64) -> bool {
+        match Self::_delegation_expiry(env, account) {
+            Some(expiry) => expiry <= block_number,
+            None => false,
+        }
+    }
+
+    /// Accounts holding a time-bound delegation to a delegatee that has not been settled yet
+    fn _timed_delegators(env: &Env, delegatee: &Address) -> Vec<Address> {
+        env.storage()
+            .persistent()
+            .get(&(symbol_short!("DLGTMD"), delegatee.clone()))
+            .unwrap_or(Vec::new(env))
+    }
+
+    /// Make room for a new entry in a delegatee's pending list
+    /// @notice When the list is full, the first expired delegation found is settled; if none has
+    /// expired the delegation is rejected. One settlement frees the one slot needed.
+    fn _reserve_timed_slot(env: &Env, delegator: &Address, delegatee: &Address) {
+        let timed = Self::_timed_delegators(env, delegatee);
+        if timed.len() < MAX_TIMED_DELEGATORS || timed.contains(delegator) {
+            return;
+        }
+        let current_block = env.ledger().sequence() as u64;
+        match timed.iter().find(|pending| Self::_is_expired(env, pending, current_block)) {
+            Some(pending) => {
+                Self::_settle_expired(env, &pending);
+            }
+            None => panic_with_error!(env, GovernanceError::TimedDelegationLimit),
+        }
+    }
+
+    /// Drop a delegator's expiry record and its entry in the delegatee's pending list
+    fn _clear_expiry(env: &Env, delegator: &Address, delegatee: &Address) {
+        if Self::_delegation_expiry(env, delegator).is_none() {
+            return;
+        }
+        env.storage().persistent().remove(&(symbol_short!("DLGEXP"), delegator.clone()));
+
+        let mut timed = Self::_timed_delegators(env, delegatee);
+        if let Some(index) = timed.first_index_of(delegator) {
+            timed.remove(index);
+        }
+        env.storage().persistent().set(&(symbol_short!("DLGTMD"), delegatee.clone()), &timed);
+    }
+
+    /// Apply unsettled expired delegations to the votes of the checkpoint in force at `block_number`
+    /// @notice A delegator's balance can't change while its delegation is unsettled (any transfer
+    /// settles first), so the current balance is the amount still credited to the delegatee.
+    /// Checkpoints written at or after an expiry already include it, so only expiries after
+    /// `since` (the in-force checkpoint's block) are applied.
+    fn _apply_expired(env: &Env, account: &Address, since: Option<u64>, block_number: u64, votes: u128) -> u128 {
+        let applies = |expiry: u64| expiry <= block_number && since.is_none_or(|since| since < expiry);
+
+        let mut votes = votes;
+        if Self::_delegation_expiry(env, account).is_some_and(applies) {
+            votes = votes.saturating_add(Self::_get_balance(env, account));
+        }
+        for delegator in Self::_timed_delegators(env, account).iter() {
+            if Self::_delegation_expiry(env, &delegator).is_some_and(applies) {
+                votes = votes.saturating_sub(Self::_get_balance(env, &delegator));
+            }
+        }
+        votes
+    }
+
+    /// Return an expired delegation's voting power to the delegator
+    /// @notice Votes read at any block are the same before and after settling; see `_record_expiry`
+    fn _settle_expired(env: &Env, delegator: &Address) -> bool {
+        let current_block = env.ledger().sequence() as u64;
+        let expiry = match Self::_delegation_expiry(env, delegator) {
+            Some(expiry) if expiry <= current_block => expiry,
+            _ => return false,
+        };
+
+        let delegatee = Self::_delegates(env, delegator);
+        let amount = Self::_get_balance(env, delegator);
+        if amount > 0 {
+            // Recorded while the expiry record still applies on read
+            Self::_record_expiry(env, &delegatee, expiry, amount, false);
+            Self::_record_expiry(env, delegator, expiry, amount, true);
+        }
+
+        Self::_clear_expiry(env, delegator, &delegatee);
+        env.storage().persistent().remove(&(symbol_short!("DELEGAT"), delegator.clone()));
+        Self::_remove_delegator(env, &delegatee, delegator);
+
+        // Emit DelegateChanged event
+        env.events().publish(
+            (symbol_short!("DelChg"), delegator.clone()),
+            (delegatee.clone(), delegator.clone())
+        );
+
+        if amount > 0 {
+            for (account, increase) in [(delegatee, false), (delegator.clone(), true)] {
+                let new_votes = Self::_votes_at(env, &account, current_block);
+                let old_votes = if increase {
+                    new_votes.saturating_sub(amount)
+                } else {
+                    new_votes.saturating_add(amount)
+                };
+                Self::_update_top_delegates(env, &account, new_votes);
+
+                // Emit DelegateVotesChanged event
+                env.events().publish(
+                    (symbol_short!("DelVotes"), account),
+                    (old_votes, new_votes)
+                );
+            }
+        }
+        true
+    }
+
+    /// Make a delegation expiry permanent in an account's checkpoints
+    /// @notice Checkpoints from the expiry onwards already include it, so only the stretch between
+    /// the expiry and the next checkpoint needs recording: one checkpoint appended at the expiry
+    /// when there is no later one, otherwise an adjustment on the next checkpoint. Nothing is
+    /// shifted or rewritten, so the cost doesn't grow with the account's history.
+    fn _record_expiry(env: &Env, account: &Address, expiry: u64, amount: u128, increase: bool) {
+        let num_checkpoints = Self::_num_checkpoints(env, account);
+        let next = Self::_checkpoints_up_to(env, account, expiry - 1);
+
+        if next == num_checkpoints {
+            let votes = Self::_votes_at(env, account, expiry);
+            Self::_set_checkpoint(env, account, next, &Checkpoint { from_block: expiry, votes });
+            env.storage().persistent().set(&(symbol_short!("NUMCHK"), account.clone()), &(num_checkpoints + 1));
+        } else if Self::_get_checkpoint(env, account, next).from_block > expiry {
+            let mut adjustments = Self::_adjustments(env, account, next);
+            adjustments.push_back(VoteAdjustment { from_block: expiry, amount, increase });
+            let slot = Self::_checkpoint_base(env, account) + next;
+            env.storage().persistent().set(&(symbol_short!("CHKADJ"), account.clone(), slot), &adjustments);
+        }
+    }
+
     fn _num_checkpoints(env: &Env, account: &Address) -> u32 {
         env.storage()
             .persistent()
@@ -270,16 +540,219 @@ impl GovernanceToken {
             .unwrap_or(0)
     }
 
+    fn _checkpoint_horizon(env: &Env, account: &Address) -> u64 {
+        env.storage()
+            .persistent()
+            .get(&(symbol_short!("CHKHZN"), account.clone()))
+            .unwrap_or(0)
+    }
+
+    /// Storage slot of an account's first checkpoint (advanced by compression)
+    fn _checkpoint_base(env: &Env, account: &Address) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&(symbol_short!("CHKBASE"), account.clone()))
+            .unwrap_or(0)
+    }
+
     fn _get_checkpoint(env: &Env, account: &Address, index: u32) -> Checkpoint {
+        let slot = Self::_checkpoint_base(env, account) + index;
+        env.storage()
+            .persistent()
+            .get(&(symbol_short!("CHKPT"), account.clone(), slot))
+            .unwrap()
+    }
+
+    fn _set_checkpoint(env: &Env, account: &Address, index: u32, checkpoint: &Checkpoint) {
+        let slot = Self::_checkpoint_base(env, account) + index;
+        env.storage().persistent().set(&(symbol_short!("CHKPT"), account.clone(), slot), checkpoint);
+    }
+
+    fn _delegator_count(env: &Env, delegatee: &Address) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&(symbol_short!("DLGCNT"), delegatee.clone()))
+            .unwrap_or(0)
+    }
+
+    fn _get_delegator(env: &Env, delegatee: &Address, index: u32) -> Address {
         env.storage()
             .persistent()
-            .get(&(symbol_short!("CHKPT"), account.clone(), index))
+            .get(&(symbol_short!("DLGTOR"), delegatee.clone(), index))
             .unwrap()
     }
 
+    /// Append a delegator to a delegatee's index
+    fn _add_delegator(env: &Env, delegatee: &Address, delegator: &Address) {
+        let count = Self::_delegator_count(env, delegatee);
+        env.storage().persistent().set(&(symbol_short!("DLGTOR"), delegatee.clone(), count), delegator);
+        env.storage().persistent().set(&(symbol_short!("DLGPOS"), delegator.clone()), &count);
+        env.storage().persistent().set(&(symbol_short!("DLGCNT"), delegatee.clone()), &(count + 1));
+    }
+
+    /// Remove a delegator from a delegatee's index
+    /// @notice Swap-remove: the last entry takes the freed slot, so removal is O(1)
+    fn _remove_delegator(env: &Env, delegatee: &Address, delegator: &Address) {
+        let position: Option<u32> = env.storage()
+            .persistent()
+            .get(&(symbol_short!("DLGPOS"), delegator.clone()));
+        let position = match position {
+            Some(position) => position,
+            None => return,
+        };
+
+        let last = Self::_delegator_count(env, delegatee) - 1;
+        if position != last {
+            let moved = Self::_get_delegator(env, delegatee, last);
+            env.storage().persistent().set(&(symbol_short!("DLGTOR"), delegatee.clone(), position), &moved);
+            env.storage().persistent().set(&(symbol_short!("DLGPOS"), moved), &position);
+        }
+        env.storage().persistent().remove(&(symbol_short!("DLGTOR"), delegatee.clone(), last));
+        env.storage().persistent().remove(&(symbol_short!("DLGPOS"), delegator.clone()));
+        env.storage().persistent().set(&(symbol_short!("DLGCNT"), delegatee.clone()), &last);
+    }
+
+    fn _top_delegates(env: &Env) -> Vec<(Address, u128)> {
+        env.storage()
+            .persistent()
+            .get(&symbol_short!("TOPDLG"))
+            .unwrap_or(Vec::new(env))
+    }
+
+    /// Re-rank an account in the top-delegates leaderboard after its votes change
+    fn _update_top_delegates(env: &Env, account: &Address, new_votes: u128) {
+        let mut leaderboard = Self::_top_delegates(env);
+
+        if let Some(index) = leaderboard.iter().position(|(delegate, _)| delegate == *account) {
+            leaderboard.remove(index as u32);
+        }
+
+        if new_votes > 0 {
+            // Leaderboard is sorted by votes descending; ties keep the earlier entry first
+            let insert_at = leaderboard
+                .iter()
+                .position(|(_, votes)| votes < new_votes)
+                .map(|i| i as u32)
+                .unwrap_or(leaderboard.len());
+            if insert_at < TOP_DELEGATES_CAP {
+                leaderboard.insert(insert_at, (account.clone(), new_votes));
+                if leaderboard.len() > TOP_DELEGATES_CAP {
+                    leaderboard.pop_back();
+                }
+            }
+        }
+
+        env.storage().persistent().set(&symbol_short!("TOPDLG"), &leaderboard);
+    }
+
+    /// Number of checkpoints with `from_block <= block_number` (binary search)
+    fn _checkpoints_up_to(env: &Env, account: &Address, block_number: u64) -> u32 {
+        let num_checkpoints = Self::_num_checkpoints(env, account);
+        if num_checkpoints == 0 {
+            return 0;
+        }
+
+        // Most reads are for recent blocks
+        if Self::_get_checkpoint(env, account, num_checkpoints - 1).from_block <= block_number {
+            return num_checkpoints;
+        }
+
+        let mut lower = 0u32;
+        let mut upper = num_checkpoints - 1;
+        while upper > lower {
+            let center = lower + (upper - lower) / 2;
+            if Self::_get_checkpoint(env, account, center).from_block <= block_number {
+                lower = center + 1;
+            } else {
+                upper = center;
+            }
+        }
+        lower
+    }
+
+    /// Adjustments recorded on the checkpoint at `index` (they apply before it takes effect)
+    fn _adjustments(env: &Env, account: &Address, index: u32) -> Vec<VoteAdjustment> {
+        let slot = Self::_checkpoint_base(env, account) + index;
+        env.storage()
+            .persistent()
+            .get(&(symbol_short!("CHKADJ"), account.clone(), slot))
+            .unwrap_or(Vec::new(env))
+    }
+
+    /// Voting power at a block: the checkpoint in force, with the adjustments recorded before the
+    /// next checkpoint and any unsettled expired delegations applied
+    fn _votes_at(env: &Env, account: &Address, block_number: u64) -> u128 {
+        let count = Self::_checkpoints_up_to(env, account, block_number);
+        let (mut votes, since) = if count > 0 {
+            let checkpoint = Self::_get_checkpoint(env, account, count - 1);
+            (checkpoint.votes, Some(checkpoint.from_block))
+        } else {
+            (0, None)
+        };
+
+        if count < Self::_num_checkpoints(env, account) {
+            for adjustment in Self::_adjustments(env, account, count).iter() {
+                if adjustment.from_block <= block_number {
+                    votes = if adjustment.increase {
+                        votes.saturating_add(adjustment.amount)
+                    } else {
+                        votes.saturating_sub(adjustment.amount)
+                    };
+                }
+            }
+        }
+
+        Self::_apply_expired(env, account, since, block_number, votes)
+    }
+
+    /// Change a delegation, optionally bounded by an expiry ledger
+    fn _delegate(env: &Env, delegator: &Address, delegatee: &Address, expiry: Option<u64>) {
+        Self::_settle_expired(env, delegator);
+
+        let current_delegate = Self::_delegates(env, delegator);
+        let delegator_balance = Self::_get_balance(env, delegator);
+
+        // A new delegation replaces any pending expiry of the previous one
+        Self::_clear_expiry(env, delegator, &current_delegate);
+        
+        // Update delegate mapping
+        env.storage().persistent().set(&(symbol_short!("DELEGAT"), delegator.clone()), delegatee);
+
+        // Keep the per-delegatee delegator index in sync
+        if current_delegate != *delegator {
+            Self::_remove_delegator(env, &current_delegate, delegator);
+        }
+        if delegatee != delegator {
+            Self::_add_delegator(env, delegatee, delegator);
+        }
+
+        if let Some(expiry) = expiry {
+            if delegatee != delegator {
+                env.storage().persistent().set(&(symbol_short!("DLGEXP"), delegator.clone()), &expiry);
+                let mut timed = Self::_timed_delegators(env, delegatee);
+                timed.push_back(delegator.clone());
+                env.storage().persistent().set(&(symbol_short!("DLGTMD"), delegatee.clone()), &timed);
+
+                // Emit DelegationExpiry event
+                env.events().publish((symbol_short!("DelExp"), delegator.clone()), (delegatee.clone(), expiry));
+            }
+        }
+        
+        // Emit DelegateChanged event
+        env.events().publish(
+            (symbol_short!("DelChg"), delegator.clone()),
+            (current_delegate.clone(), delegatee.clone())
+        );
+        
+        // Move voting power
+        Self::_move_delegates(env, &current_delegate, delegatee, delegator_balance);
+    }
+
     /// Write a new checkpoint for an account
     /// @notice This uses Compound's checkpoint pattern for gas efficiency
     fn _write_checkpoint(env: &Env, account: &Address, new_votes: u128) {
+        Self::_update_top_delegates(env, account, new_votes);
+
         let current_block = env.ledger().sequence() as u64;
         let num_checkpoints = Self::_num_checkpoints(env, account);
 
@@ -288,25 +761,19 @@ impl GovernanceToken {
             
             // If the block is the same, just update the last checkpoint
             if last_checkpoint.from_block == current_block {
-                env.storage().persistent().set(
-                    &(symbol_short!("CHKPT"), account.clone(), num_checkpoints - 1),
-                    &Checkpoint {
-                        from_block: current_block,
-                        votes: new_votes,
-                    }
-                );
+                Self::_set_checkpoint(env, account, num_checkpoints - 1, &Checkpoint {
+                    from_block: current_block,
+                    votes: new_votes,
+                });
                 return;
             }
         }
 
         // Create new checkpoint
-        env.storage().persistent().set(
-            &(symbol_short!("CHKPT"), account.clone(), num_checkpoints),
-            &Checkpoint {
-                from_block: current_block,
-                votes: new_votes,
-            }
-        );
+        Self::_set_checkpoint(env, account, num_checkpoints, &Checkpoint {
+            from_block: current_block,
+            votes: new_votes,
+        });
         env.storage().persistent().set(&(symbol_short!("NUMCHK"), account.clone()), &(num_checkpoints + 1));
     }
 
@@ -315,8 +782,9 @@ impl GovernanceToken {
     fn _move_delegates(env: &Env, from: &Address, to: &Address, amount: u128) {
         if from != to && amount > 0 {
             // Decrease votes for source delegate
-            let from_votes = Self::get_current_votes(env.clone(), from.clone());
-            let new_from_votes = if from_votes >= amount { from_votes - amount } else { 0 };
+            let current_block = env.ledger().sequence() as u64;
+            let from_votes = Self::_votes_at(env, from, current_block);
+            let new_from_votes = from_votes.saturating_sub(amount);
             Self::_write_checkpoint(env, from, new_from_votes);
             
             // Emit DelegateVotesChanged event
@@ -326,7 +794,7 @@ impl GovernanceToken {
             );
             
             // Increase votes for destination delegate
-            let to_votes = Self::get_current_votes(env.clone(), to.clone());
+            let to_votes = Self::_votes_at(env, to, current_block);
             let new_to_votes = to_votes.checked_add(amount).expect("Overflow in votes");
             Self::_write_checkpoint(env, to, new_to_votes);
             
@@ -484,5 +952,580 @@ mod tests {
         // Try to transfer more than balance
         client.transfer(&admin, &user, &2_000u128);
     }
-}
 
+    #[test]
+    fn test_compress_checkpoints_preserves_retained_range() {
+        let env = Env::default();
+        env.ledger().with_mut(|li| {
+            li.sequence_number = 100;
+            // Keep entries live across the retention window
+            li.min_persistent_entry_ttl = 2 * MIN_RETAIN_LEDGERS as u32;
+            li.max_entry_ttl = 4 * MIN_RETAIN_LEDGERS as u32;
+        });
+
+        let contract_id = env.register_contract(None, GovernanceToken);
+        let client = GovernanceTokenClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let user = Address::generate(&env);
+
+        env.mock_all_auths();
+        client.initialize(
+            &admin,
+            &String::from_str(&env, "Gov"),
+            &String::from_str(&env, "GOV"),
+            &18,
+            &1_000_000u128
+        );
+
+        // Checkpoints at blocks 100, 200, 300, 400, 500
+        for block in [200u32, 300, 400, 500] {
+            env.ledger().with_mut(|li| li.sequence_number = block);
+            client.transfer(&admin, &user, &100_000u128);
+        }
+        assert_eq!(client.num_checkpoints(&admin), 5);
+
+        // Cutoff is block 350, so 100 and 200 merge into 300
+        let now = 350 + MIN_RETAIN_LEDGERS as u32;
+        env.ledger().with_mut(|li| li.sequence_number = now);
+        let removed = client.compress_checkpoints(&admin, &admin, &MIN_RETAIN_LEDGERS);
+        assert_eq!(removed, 2);
+        assert_eq!(client.num_checkpoints(&admin), 3);
+        assert_eq!(client.checkpoint_horizon(&admin), 300);
+
+        // Retained range answers exactly as before
+        assert_eq!(client.get_prior_votes(&admin, &300), 800_000);
+        assert_eq!(client.get_prior_votes(&admin, &350), 800_000);
+        assert_eq!(client.get_prior_votes(&admin, &450), 700_000);
+        assert_eq!(client.get_prior_votes(&admin, &(now as u64 - 1)), 600_000);
+        assert_eq!(client.get_current_votes(&admin), 600_000);
+
+        // New checkpoints continue after the compacted entries
+        env.ledger().with_mut(|li| li.sequence_number = now + 100);
+        client.transfer(&admin, &user, &100_000u128);
+        assert_eq!(client.num_checkpoints(&admin), 4);
+        assert_eq!(client.get_prior_votes(&admin, &(now as u64 + 50)), 600_000);
+        env.ledger().with_mut(|li| li.sequence_number = now + 101);
+        assert_eq!(client.get_prior_votes(&admin, &(now as u64 + 100)), 500_000);
+    }
+
+    #[test]
+    fn test_compress_checkpoints_frees_storage_in_batches() {
+        let env = Env::default();
+        env.ledger().with_mut(|li| {
+            li.sequence_number = 100;
+            // Keep entries live across the retention window
+            li.min_persistent_entry_ttl = 2 * MIN_RETAIN_LEDGERS as u32;
+            li.max_entry_ttl = 4 * MIN_RETAIN_LEDGERS as u32;
+        });
+
+        let contract_id = env.register_contract(None, GovernanceToken);
+        let client = GovernanceTokenClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let user = Address::generate(&env);
+
+        env.mock_all_auths();
+        client.initialize(
+            &admin,
+            &String::from_str(&env, "Gov"),
+            &String::from_str(&env, "GOV"),
+            &18,
+            &1_000_000u128
+        );
+
+        for block in 101u32..=125 {
+            env.ledger().with_mut(|li| li.sequence_number = block);
+            client.transfer(&admin, &user, &1_000u128);
+        }
+        assert_eq!(client.num_checkpoints(&admin), 26);
+
+        // The whole history is past the cutoff; the first pass stops at the batch limit
+        env.ledger().with_mut(|li| li.sequence_number = 200 + MIN_RETAIN_LEDGERS as u32);
+        assert_eq!(client.compress_checkpoints(&admin, &admin, &MIN_RETAIN_LEDGERS), COMPRESS_BATCH_LIMIT);
+        assert_eq!(client.num_checkpoints(&admin), 6);
+        assert_eq!(client.checkpoint_horizon(&admin), 120);
+        assert_eq!(client.get_prior_votes(&admin, &122), 978_000);
+
+        assert_eq!(client.compress_checkpoints(&admin, &admin, &MIN_RETAIN_LEDGERS), 5);
+        assert_eq!(client.num_checkpoints(&admin), 1);
+        assert_eq!(client.checkpoint_horizon(&admin), 125);
+
+        env.as_contract(&contract_id, || {
+            let storage = env.storage().persistent();
+            assert!(storage.has(&(symbol_short!("CHKPT"), admin.clone(), 25u32)));
+            for i in 0u32..25 {
+                assert!(!storage.has(&(symbol_short!("CHKPT"), admin.clone(), i)));
+            }
+        });
+
+        // Nothing left to merge
+        assert_eq!(client.compress_checkpoints(&admin, &admin, &MIN_RETAIN_LEDGERS), 0);
+        assert_eq!(client.get_current_votes(&admin), 975_000);
+    }
+
+    #[test]
+    fn test_compress_checkpoints_rejects_short_retention() {
+        let env = Env::default();
+        env.ledger().with_mut(|li| {
+            li.sequence_number = 100;
+            // Keep entries live across the retention window
+            li.min_persistent_entry_ttl = 2 * MIN_RETAIN_LEDGERS as u32;
+            li.max_entry_ttl = 4 * MIN_RETAIN_LEDGERS as u32;
+        });
+
+        let contract_id = env.register_contract(None, GovernanceToken);
+        let client = GovernanceTokenClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+
+        env.mock_all_auths();
+        client.initialize(
+            &admin,
+            &String::from_str(&env, "Gov"),
+            &String::from_str(&env, "GOV"),
+            &18,
+            &1_000_000u128
+        );
+
+        assert_eq!(
+            client.try_compress_checkpoints(&admin, &admin, &(MIN_RETAIN_LEDGERS - 1)),
+            Err(Ok(GovernanceError::InvalidCheckpoint.into()))
+        );
+    }
+
+    #[test]
+    fn test_prior_votes_before_horizon_rejected() {
+        let env = Env::default();
+        env.ledger().with_mut(|li| {
+            li.sequence_number = 100;
+            // Keep entries live across the retention window
+            li.min_persistent_entry_ttl = 2 * MIN_RETAIN_LEDGERS as u32;
+            li.max_entry_ttl = 4 * MIN_RETAIN_LEDGERS as u32;
+        });
+
+        let contract_id = env.register_contract(None, GovernanceToken);
+        let client = GovernanceTokenClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let user = Address::generate(&env);
+
+        env.mock_all_auths();
+        client.initialize(
+            &admin,
+            &String::from_str(&env, "Gov"),
+            &String::from_str(&env, "GOV"),
+            &18,
+            &1_000_000u128
+        );
+
+        env.ledger().with_mut(|li| li.sequence_number = 200);
+        client.transfer(&admin, &user, &100_000u128);
+
+        env.ledger().with_mut(|li| li.sequence_number = 250 + MIN_RETAIN_LEDGERS as u32);
+        client.compress_checkpoints(&admin, &admin, &MIN_RETAIN_LEDGERS);
+
+        // Block 150 predates the merged entry at block 200
+        assert_eq!(
+            client.try_get_prior_votes(&admin, &150),
+            Err(Ok(GovernanceError::InvalidCheckpoint.into()))
+        );
+    }
+
+    #[test]
+    fn test_compress_checkpoints_admin_only() {
+        let env = Env::default();
+        env.ledger().with_mut(|li| li.sequence_number = 100);
+
+        let contract_id = env.register_contract(None, GovernanceToken);
+        let client = GovernanceTokenClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let attacker = Address::generate(&env);
+
+        env.mock_all_auths();
+        client.initialize(
+            &admin,
+            &String::from_str(&env, "Gov"),
+            &String::from_str(&env, "GOV"),
+            &18,
+            &1_000_000u128
+        );
+
+        assert_eq!(
+            client.try_compress_checkpoints(&attacker, &admin, &MIN_RETAIN_LEDGERS),
+            Err(Ok(GovernanceError::Unauthorized.into()))
+        );
+    }
+
+    #[test]
+    fn test_get_delegators_pagination_and_redelegation() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, GovernanceToken);
+        let client = GovernanceTokenClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let delegatee = Address::generate(&env);
+        let other = Address::generate(&env);
+
+        env.mock_all_auths();
+        client.initialize(
+            &admin,
+            &String::from_str(&env, "Gov"),
+            &String::from_str(&env, "GOV"),
+            &18,
+            &1_000_000u128
+        );
+
+        let mut holders = Vec::new(&env);
+        for _ in 0..5 {
+            let holder = Address::generate(&env);
+            client.transfer(&admin, &holder, &10_000u128);
+            client.delegate(&holder, &delegatee);
+            holders.push_back(holder);
+        }
+        assert_eq!(client.delegator_count(&delegatee), 5);
+
+        // Pages cover every delegator exactly once
+        let first = client.get_delegators(&delegatee, &0, &3);
+        let second = client.get_delegators(&delegatee, &3, &3);
+        assert_eq!(first.len(), 3);
+        assert_eq!(second.len(), 2);
+        for holder in holders.iter() {
+            assert!(first.contains(&holder) || second.contains(&holder));
+        }
+        assert_eq!(client.get_delegators(&delegatee, &10, &3).len(), 0);
+
+        // Re-delegating moves the entry between indexes
+        let mover = holders.get(1).unwrap();
+        client.delegate(&mover, &other);
+        assert_eq!(client.delegator_count(&delegatee), 4);
+        assert!(!client.get_delegators(&delegatee, &0, &10).contains(&mover));
+        assert_eq!(client.get_delegators(&other, &0, &10), Vec::from_array(&env, [mover.clone()]));
+
+        // Delegating back to self only removes the entry
+        client.delegate(&mover, &mover);
+        assert_eq!(client.delegator_count(&other), 0);
+        assert_eq!(client.get_current_votes(&delegatee), 40_000);
+    }
+
+    #[test]
+    fn test_top_delegates_ranking() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, GovernanceToken);
+        let client = GovernanceTokenClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let alice = Address::generate(&env);
+        let bob = Address::generate(&env);
+        let carol = Address::generate(&env);
+
+        env.mock_all_auths();
+        client.initialize(
+            &admin,
+            &String::from_str(&env, "Gov"),
+            &String::from_str(&env, "GOV"),
+            &18,
+            &1_000_000u128
+        );
+        client.delegate(&admin, &alice);
+        assert_eq!(
+            client.top_delegates(&10),
+            Vec::from_array(&env, [(alice.clone(), 1_000_000u128)])
+        );
+
+        // Holders delegating to bob and carol reshuffle the ranking
+        let holder = Address::generate(&env);
+        client.transfer(&admin, &holder, &600_000u128);
+        client.delegate(&holder, &bob);
+        client.transfer(&admin, &carol, &100_000u128);
+
+        let top = client.top_delegates(&10);
+        assert_eq!(top.len(), 3);
+        assert_eq!(top.get(0).unwrap(), (bob.clone(), 600_000u128));
+        assert_eq!(top.get(1).unwrap(), (alice.clone(), 300_000u128));
+        assert_eq!(top.get(2).unwrap(), (carol.clone(), 100_000u128));
+
+        // Limit truncates, and accounts that drop to zero leave the board
+        assert_eq!(client.top_delegates(&1).len(), 1);
+        client.delegate(&holder, &holder);
+        let top = client.top_delegates(&10);
+        assert_eq!(top.get(0).unwrap(), (holder.clone(), 600_000u128));
+        assert!(!top.iter().any(|(delegate, _)| delegate == bob));
+    }
+
+    #[test]
+    fn test_top_delegates_capped() {
+        let env = Env::default();
+        env.budget().reset_unlimited();
+        let contract_id = env.register_contract(None, GovernanceToken);
+        let client = GovernanceTokenClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+
+        env.mock_all_auths();
+        client.initialize(
+            &admin,
+            &String::from_str(&env, "Gov"),
+            &String::from_str(&env, "GOV"),
+            &18,
+            &1_000_000u128
+        );
+
+        for i in 1..=(TOP_DELEGATES_CAP + 5) {
+            client.transfer(&admin, &Address::generate(&env), &(i as u128));
+        }
+
+        let top = client.top_delegates(&u32::MAX);
+        assert_eq!(top.len(), TOP_DELEGATES_CAP);
+        assert_eq!(top.get(0).unwrap(), (admin.clone(), client.get_current_votes(&admin)));
+        // The smallest holders fell off the bottom
+        assert_eq!(top.get(TOP_DELEGATES_CAP - 1).unwrap().1, 7);
+    }
+
+    #[test]
+    fn test_delegate_with_expiry_reverts_power() {
+        let env = Env::default();
+        env.ledger().with_mut(|li| li.sequence_number = 100);
+
+        let contract_id = env.register_contract(None, GovernanceToken);
+        let client = GovernanceTokenClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let holder = Address::generate(&env);
+        let delegatee = Address::generate(&env);
+
+        env.mock_all_auths();
+        client.initialize(
+            &admin,
+            &String::from_str(&env, "Gov"),
+            &String::from_str(&env, "GOV"),
+            &18,
+            &1_000_000u128
+        );
+        client.transfer(&admin, &holder, &100_000u128);
+        client.delegate_with_expiry(&holder, &delegatee, &200u64);
+
+        assert_eq!(client.get_current_votes(&delegatee), 100_000);
+        assert_eq!(client.get_current_votes(&holder), 0);
+        assert_eq!(client.delegation_expiry(&holder), Some(200));
+
+        // Expired but not yet settled: reads already apply the expiry
+        env.ledger().with_mut(|li| li.sequence_number = 250);
+        assert_eq!(client.delegates(&holder), holder);
+        assert_eq!(client.get_current_votes(&delegatee), 0);
+        assert_eq!(client.get_current_votes(&holder), 100_000);
+        assert_eq!(client.get_prior_votes(&delegatee, &199), 100_000);
+        assert_eq!(client.get_prior_votes(&delegatee, &200), 0);
+        assert_eq!(client.get_prior_votes(&holder, &199), 0);
+        assert_eq!(client.get_prior_votes(&holder, &200), 100_000);
+
+        // The holder's next transfer settles the delegation, back-dating checkpoints to the expiry
+        client.transfer(&holder, &admin, &10_000u128);
+        assert_eq!(client.delegation_expiry(&holder), None);
+        assert_eq!(client.delegator_count(&delegatee), 0);
+        assert_eq!(client.get_current_votes(&holder), 90_000);
+        assert_eq!(client.get_current_votes(&delegatee), 0);
+
+        env.ledger().with_mut(|li| li.sequence_number = 260);
+        assert_eq!(client.get_prior_votes(&delegatee, &199), 100_000);
+        assert_eq!(client.get_prior_votes(&delegatee, &220), 0);
+        assert_eq!(client.get_prior_votes(&holder, &220), 100_000);
+        assert_eq!(client.get_prior_votes(&holder, &250), 90_000);
+        assert!(!client.settle_delegation(&holder));
+    }
+
+    #[test]
+    fn test_expired_delegation_settles_around_later_checkpoints() {
+        let env = Env::default();
+        env.ledger().with_mut(|li| li.sequence_number = 100);
+
+        let contract_id = env.register_contract(None, GovernanceToken);
+        let client = GovernanceTokenClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let timed = Address::generate(&env);
+        let other = Address::generate(&env);
+        let delegatee = Address::generate(&env);
+
+        env.mock_all_auths();
+        client.initialize(
+            &admin,
+            &String::from_str(&env, "Gov"),
+            &String::from_str(&env, "GOV"),
+            &18,
+            &1_000_000u128
+        );
+        client.transfer(&admin, &timed, &100_000u128);
+        client.transfer(&admin, &other, &50_000u128);
+        client.delegate_with_expiry(&timed, &delegatee, &200u64);
+
+        // A plain delegation after the expiry writes a checkpoint while the timed one is unsettled
+        env.ledger().with_mut(|li| li.sequence_number = 300);
+        client.delegate(&other, &delegatee);
+        assert_eq!(client.get_current_votes(&delegatee), 50_000);
+
+        env.ledger().with_mut(|li| li.sequence_number = 400);
+        assert!(client.settle_delegation(&timed));
+
+        env.ledger().with_mut(|li| li.sequence_number = 401);
+        assert_eq!(client.get_prior_votes(&delegatee, &150), 100_000);
+        assert_eq!(client.get_prior_votes(&delegatee, &250), 0);
+        assert_eq!(client.get_prior_votes(&delegatee, &350), 50_000);
+        assert_eq!(client.get_prior_votes(&timed, &150), 0);
+        assert_eq!(client.get_prior_votes(&timed, &250), 100_000);
+        assert_eq!(client.get_current_votes(&delegatee), 50_000);
+        assert_eq!(client.get_current_votes(&timed), 100_000);
+        assert_eq!(client.top_delegates(&10).get(1).unwrap(), (timed.clone(), 100_000u128));
+    }
+
+    #[test]
+    fn test_delegate_with_expiry_validation_and_replacement() {
+        let env = Env::default();
+        env.ledger().with_mut(|li| li.sequence_number = 100);
+
+        let contract_id = env.register_contract(None, GovernanceToken);
+        let client = GovernanceTokenClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let delegatee = Address::generate(&env);
+
+        env.mock_all_auths();
+        client.initialize(
+            &admin,
+            &String::from_str(&env, "Gov"),
+            &String::from_str(&env, "GOV"),
+            &18,
+            &1_000_000u128
+        );
+
+        assert_eq!(
+            client.try_delegate_with_expiry(&admin, &delegatee, &100u64),
+            Err(Ok(GovernanceError::InvalidExpiry.into()))
+        );
+
+        // A plain delegation before the expiry makes it permanent
+        client.delegate_with_expiry(&admin, &delegatee, &200u64);
+        client.delegate(&admin, &delegatee);
+        assert_eq!(client.delegation_expiry(&admin), None);
+
+        env.ledger().with_mut(|li| li.sequence_number = 300);
+        assert_eq!(client.delegates(&admin), delegatee);
+        assert_eq!(client.get_current_votes(&delegatee), 1_000_000);
+        assert_eq!(client.get_prior_votes(&delegatee, &250), 1_000_000);
+    }
+
+    #[test]
+    fn test_delegate_with_expiry_rejects_empty_and_caps_pending_list() {
+        let env = Env::default();
+        env.ledger().with_mut(|li| li.sequence_number = 100);
+        env.budget().reset_unlimited();
+
+        let contract_id = env.register_contract(None, GovernanceToken);
+        let client = GovernanceTokenClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let delegatee = Address::generate(&env);
+
+        env.mock_all_auths();
+        client.initialize(
+            &admin,
+            &String::from_str(&env, "Gov"),
+            &String::from_str(&env, "GOV"),
+            &18,
+            &1_000_000u128
+        );
+
+        // Accounts without tokens can't pad the delegatee's list
+        let empty = Address::generate(&env);
+        assert_eq!(
+            client.try_delegate_with_expiry(&empty, &delegatee, &200u64),
+            Err(Ok(GovernanceError::InsufficientBalance.into()))
+        );
+
+        // Fill the list: one delegation expiring soon, the rest later
+        let mut delegators = Vec::new(&env);
+        for i in 0..MAX_TIMED_DELEGATORS {
+            let delegator = Address::generate(&env);
+            client.transfer(&admin, &delegator, &1_000u128);
+            let expiry = if i == 0 || i == 2 { 150u64 } else { 1_000u64 };
+            client.delegate_with_expiry(&delegator, &delegatee, &expiry);
+            delegators.push_back(delegator);
+        }
+
+        let late = Address::generate(&env);
+        client.transfer(&admin, &late, &1_000u128);
+        assert_eq!(
+            client.try_delegate_with_expiry(&late, &delegatee, &1_000u64),
+            Err(Ok(GovernanceError::TimedDelegationLimit.into()))
+        );
+
+        // Re-delegating an entry already in the list is still allowed
+        client.delegate_with_expiry(&delegators.get(1).unwrap(), &delegatee, &2_000u64);
+
+        // Once entries have expired, just one is settled to make room
+        env.ledger().with_mut(|li| li.sequence_number = 160);
+        client.delegate_with_expiry(&late, &delegatee, &1_000u64);
+        assert_eq!(client.delegation_expiry(&delegators.get(0).unwrap()), None);
+        assert_eq!(client.delegates(&delegators.get(0).unwrap()), delegators.get(0).unwrap());
+        assert_eq!(client.delegation_expiry(&delegators.get(2).unwrap()), Some(150));
+        assert_eq!(client.get_current_votes(&delegatee), 1_000 * (MAX_TIMED_DELEGATORS as u128 - 1));
+    }
+
+    #[test]
+    fn test_settlement_does_not_rewrite_history() {
+        let env = Env::default();
+        env.ledger().with_mut(|li| li.sequence_number = 100);
+        env.budget().reset_unlimited();
+
+        let contract_id = env.register_contract(None, GovernanceToken);
+        let client = GovernanceTokenClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let timed = Address::generate(&env);
+        let delegatee = Address::generate(&env);
+
+        env.mock_all_auths();
+        client.initialize(
+            &admin,
+            &String::from_str(&env, "Gov"),
+            &String::from_str(&env, "GOV"),
+            &18,
+            &1_000_000u128
+        );
+        client.transfer(&admin, &timed, &100_000u128);
+        client.delegate(&admin, &delegatee);
+        client.delegate_with_expiry(&timed, &delegatee, &200u64);
+
+        // A busy delegatee: a checkpoint every 10 ledgers across the expiry, long before settlement
+        for i in 0..30u32 {
+            env.ledger().with_mut(|li| li.sequence_number = 105 + i * 10);
+            client.transfer(&admin, &Address::generate(&env), &1_000u128);
+        }
+        let delegatee_checkpoints = client.num_checkpoints(&delegatee);
+        let timed_checkpoints = client.num_checkpoints(&timed);
+
+        env.ledger().with_mut(|li| li.sequence_number = 500);
+        let blocks = [150u64, 199, 200, 202, 205, 250, 399, 499];
+        let mut before = Vec::new(&env);
+        for block in blocks {
+            before.push_back((client.get_prior_votes(&delegatee, &block), client.get_prior_votes(&timed, &block)));
+        }
+
+        assert!(client.settle_delegation(&timed));
+
+        // Existing checkpoints are left in place: the delegatee gets one adjustment, the
+        // delegator one appended checkpoint
+        assert_eq!(client.num_checkpoints(&delegatee), delegatee_checkpoints);
+        assert_eq!(client.num_checkpoints(&timed), timed_checkpoints + 1);
+
+        env.ledger().with_mut(|li| li.sequence_number = 501);
+        for (i, block) in blocks.iter().enumerate() {
+            let after = (client.get_prior_votes(&delegatee, block), client.get_prior_votes(&timed, block));
+            assert_eq!(after, before.get(i as u32).unwrap());
+        }
+        assert_eq!(client.get_prior_votes(&delegatee, &202), 1_000_000 - 100_000 - 10_000);
+        assert_eq!(client.get_prior_votes(&timed, &205), 100_000);
+        assert_eq!(client.get_current_votes(&delegatee), 1_000_000 - 100_000 - 30_000);
+    }
+}
@@ -110,14 +110,14 @@ fn test_complete_governance_flow() {
     let snapshot_block = proposal.start_block;
 
     // Cast votes and record them in proposal
-    let vote1 = voting.cast_vote(&token.address, &prop_id, &snapshot_block, &voter1, &VoteSupport::For);
-    proposals.record_vote(&prop_id, &VoteSupport::For, &vote1.votes);
+    voting.cast_vote(&token.address, &prop_id, &snapshot_block, &voter1, &VoteSupport::For);
+    voting.submit_vote(&proposals.address, &prop_id, &voter1);
     
-    let vote2 = voting.cast_vote(&token.address, &prop_id, &snapshot_block, &voter2, &VoteSupport::For);
-    proposals.record_vote(&prop_id, &VoteSupport::For, &vote2.votes);
+    voting.cast_vote(&token.address, &prop_id, &snapshot_block, &voter2, &VoteSupport::For);
+    voting.submit_vote(&proposals.address, &prop_id, &voter2);
     
-    let vote3 = voting.cast_vote(&token.address, &prop_id, &snapshot_block, &voter3, &VoteSupport::Against);
-    proposals.record_vote(&prop_id, &VoteSupport::Against, &vote3.votes);
+    voting.cast_vote(&token.address, &prop_id, &snapshot_block, &voter3, &VoteSupport::Against);
+    voting.submit_vote(&proposals.address, &prop_id, &voter3);
 
     // Verify votes recorded
     assert!(voting.has_voted(&prop_id, &voter1));
@@ -310,11 +310,11 @@ fn test_quorum_and_threshold_enforcement() {
     let snapshot_block = proposal.start_block;
 
     // Vote with insufficient quorum (only 35% of supply)
-    let vote1 = voting.cast_vote(&token.address, &prop_id, &snapshot_block, &voter1, &VoteSupport::For);
-    proposals.record_vote(&prop_id, &VoteSupport::For, &vote1.votes);
+    voting.cast_vote(&token.address, &prop_id, &snapshot_block, &voter1, &VoteSupport::For);
+    voting.submit_vote(&proposals.address, &prop_id, &voter1);
     
-    let vote2 = voting.cast_vote(&token.address, &prop_id, &snapshot_block, &voter2, &VoteSupport::For);
-    proposals.record_vote(&prop_id, &VoteSupport::For, &vote2.votes);
+    voting.cast_vote(&token.address, &prop_id, &snapshot_block, &voter2, &VoteSupport::For);
+    voting.submit_vote(&proposals.address, &prop_id, &voter2);
 
     env.ledger().with_mut(|li| li.sequence_number = 1130);
 
@@ -1,6 +1,7 @@
 #![no_std]
 
 pub mod types;
+pub mod proposal_actions;
 
 // Build one contract at a time using features
 #[cfg(any(test, feature = "token"))]
@@ -28,6 +29,13 @@ pub mod upgrade_proposal;
 #[cfg(any(test, feature = "upgrades"))]
 pub use upgrade_proposal::UpgradeManager;
 
+#[cfg(any(test, feature = "rewards"))]
+pub mod participation_rewards;
+#[cfg(any(test, feature = "rewards"))]
+pub use participation_rewards::ParticipationRewards;
+
 #[cfg(test)]
 mod integration_tests;
+#[cfg(test)]
+mod reentrancy_tests;
 
@@ -0,0 +1,183 @@
+#![allow(unused)]
+//! Proposal action builders
+//!
+//! Proposals carry raw `targets/values/calldatas`. These helpers turn typed
+//! `ProposalAction`s into well-formed entries so proposers don't hand-encode calldata.
+//!
+//! Calldata encoding: XDR of `(function: Symbol, args: Vec<Val>)`, decoded with `decode_call`.
+
+use crate::types::{ContractUpgrade, GovernanceError, ProposalAction, TreasuryTransfer};
+use soroban_sdk::xdr::{FromXdr, ToXdr};
+use soroban_sdk::{panic_with_error, symbol_short, Address, Bytes, Env, IntoVal, Symbol, TryFromVal, Val, Vec};
+
+/// XDR prefix of every `encode_call` payload: ScVal::Vec, present, 2 elements, first is ScVal::Symbol
+const CALL_HEADER: [u8; 16] = [0, 0, 0, 16, 0, 0, 0, 1, 0, 0, 0, 2, 0, 0, 0, 15];
+
+/// Encode a contract call as proposal calldata
+pub fn encode_call(env: &Env, function: Symbol, args: Vec<Val>) -> Bytes {
+    (function, args).to_xdr(env)
+}
+
+/// Decode proposal calldata back into the function name and arguments
+pub fn decode_call(env: &Env, calldata: &Bytes) -> (Symbol, Vec<Val>) {
+    try_decode_call(env, calldata)
+        .unwrap_or_else(|| panic_with_error!(env, GovernanceError::InvalidProposal))
+}
+
+/// Decode proposal calldata, returning None if it isn't an `encode_call` payload
+/// @notice The host traps on malformed XDR rather than returning an error, so the payload must
+/// start with the `encode_call` header before it is deserialized
+pub fn try_decode_call(env: &Env, calldata: &Bytes) -> Option<(Symbol, Vec<Val>)> {
+    if calldata.len() < CALL_HEADER.len() as u32
+        || calldata.slice(0..CALL_HEADER.len() as u32) != Bytes::from_array(env, &CALL_HEADER)
+    {
+        return None;
+    }
+
+    let call = Vec::<Val>::try_from_val(env, &Val::from_xdr(env, calldata).ok()?).ok()?;
+    let function = Symbol::try_from_val(env, &call.get(0)?).ok()?;
+    let args = Vec::<Val>::try_from_val(env, &call.get(1)?).ok()?;
+    Some((function, args))
+}
+
+/// Build the `(target, value, calldata)` entry for a typed action
+/// @param governance: The ProposalManager address (target of config updates)
+/// @param timelock: The timelock address, which executes calls and holds the treasury
+pub fn build_action(
+    env: &Env,
+    governance: &Address,
+    timelock: &Address,
+    action: &ProposalAction,
+) -> (Address, u128, Bytes) {
+    match action {
+        ProposalAction::UpdateConfig(config) => {
+            let args = (timelock.clone(), config.clone()).into_val(env);
+            (governance.clone(), 0, encode_call(env, Symbol::new(env, "update_config"), args))
+        }
+        ProposalAction::TreasuryTransfer(transfer) => {
+            if transfer.amount <= 0 {
+                panic_with_error!(env, GovernanceError::InvalidProposal);
+            }
+            let args = (timelock.clone(), transfer.recipient.clone(), transfer.amount).into_val(env);
+            (transfer.token.clone(), 0, encode_call(env, symbol_short!("transfer"), args))
+        }
+        ProposalAction::UpgradeContract(upgrade) => {
+            let args = (timelock.clone(), upgrade.new_wasm_hash.clone()).into_val(env);
+            (upgrade.contract.clone(), 0, encode_call(env, symbol_short!("upgrade"), args))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::GovernanceConfig;
+    use soroban_sdk::testutils::Address as _;
+    use soroban_sdk::BytesN;
+
+    #[test]
+    fn test_encode_decode_roundtrip() {
+        let env = Env::default();
+        let recipient = Address::generate(&env);
+
+        let args: Vec<Val> = (recipient.clone(), 42i128).into_val(&env);
+        let calldata = encode_call(&env, symbol_short!("transfer"), args);
+
+        let (function, decoded) = decode_call(&env, &calldata);
+        assert_eq!(function, symbol_short!("transfer"));
+        assert_eq!(decoded.len(), 2);
+        assert_eq!(Address::try_from_val(&env, &decoded.get(0).unwrap()).unwrap(), recipient);
+        assert_eq!(i128::try_from_val(&env, &decoded.get(1).unwrap()).unwrap(), 42);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_decode_rejects_malformed_calldata() {
+        let env = Env::default();
+        decode_call(&env, &Bytes::from_array(&env, &[1, 2, 3]));
+    }
+
+    #[test]
+    fn test_try_decode_rejects_foreign_payloads() {
+        let env = Env::default();
+
+        assert!(try_decode_call(&env, &Bytes::new(&env)).is_none());
+        assert!(try_decode_call(&env, &Bytes::from_array(&env, &[0xde, 0xad])).is_none());
+        // Valid XDR, but not a (Symbol, Vec) call
+        assert!(try_decode_call(&env, &(1u32, 2u32).to_xdr(&env)).is_none());
+        assert!(try_decode_call(&env, &(symbol_short!("f"), 3u32).to_xdr(&env)).is_none());
+
+        let calldata = encode_call(&env, symbol_short!("upgrade"), Vec::new(&env));
+        assert_eq!(try_decode_call(&env, &calldata), Some((symbol_short!("upgrade"), Vec::new(&env))));
+    }
+
+    #[test]
+    fn test_build_treasury_transfer() {
+        let env = Env::default();
+        let governance = Address::generate(&env);
+        let timelock = Address::generate(&env);
+        let token = Address::generate(&env);
+        let recipient = Address::generate(&env);
+
+        let action = ProposalAction::TreasuryTransfer(TreasuryTransfer {
+            token: token.clone(),
+            recipient: recipient.clone(),
+            amount: 5_000,
+        });
+        let (target, value, calldata) = build_action(&env, &governance, &timelock, &action);
+
+        assert_eq!(target, token);
+        assert_eq!(value, 0);
+        let (function, args) = decode_call(&env, &calldata);
+        assert_eq!(function, symbol_short!("transfer"));
+        assert_eq!(Address::try_from_val(&env, &args.get(0).unwrap()).unwrap(), timelock);
+        assert_eq!(Address::try_from_val(&env, &args.get(1).unwrap()).unwrap(), recipient);
+        assert_eq!(i128::try_from_val(&env, &args.get(2).unwrap()).unwrap(), 5_000);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_build_treasury_transfer_rejects_zero_amount() {
+        let env = Env::default();
+        let action = ProposalAction::TreasuryTransfer(TreasuryTransfer {
+            token: Address::generate(&env),
+            recipient: Address::generate(&env),
+            amount: 0,
+        });
+        build_action(&env, &Address::generate(&env), &Address::generate(&env), &action);
+    }
+
+    #[test]
+    fn test_build_update_config_and_upgrade() {
+        let env = Env::default();
+        let governance = Address::generate(&env);
+        let timelock = Address::generate(&env);
+
+        let config = GovernanceConfig {
+            voting_delay: 1,
+            voting_period: 2,
+            proposal_threshold: 3,
+            quorum_numerator: 4,
+            timelock_delay: 5,
+        };
+        let (target, _, calldata) =
+            build_action(&env, &governance, &timelock, &ProposalAction::UpdateConfig(config));
+        assert_eq!(target, governance);
+        let (function, args) = decode_call(&env, &calldata);
+        assert_eq!(function, Symbol::new(&env, "update_config"));
+        let decoded = GovernanceConfig::try_from_val(&env, &args.get(1).unwrap()).unwrap();
+        assert_eq!(decoded.quorum_numerator, 4);
+
+        let governed = Address::generate(&env);
+        let hash = BytesN::from_array(&env, &[7u8; 32]);
+        let upgrade = ProposalAction::UpgradeContract(ContractUpgrade {
+            contract: governed.clone(),
+            new_wasm_hash: hash.clone(),
+        });
+        let (target, _, calldata) = build_action(&env, &governance, &timelock, &upgrade);
+        assert_eq!(target, governed);
+        let (function, args) = decode_call(&env, &calldata);
+        assert_eq!(function, symbol_short!("upgrade"));
+        assert_eq!(BytesN::<32>::try_from_val(&env, &args.get(1).unwrap()).unwrap(), hash);
+    }
+}
@@ -1,9 +1,13 @@
 #![allow(unused)]
-use crate::governance_token::GovernanceTokenClient;
+use crate::governance_token::{GovernanceTokenClient, MIN_RETAIN_LEDGERS};
+use crate::proposal_actions;
 use crate::timelock::TimelockClient;
-use crate::types::{GovernanceConfig, GovernanceError, Proposal, ProposalState};
+use crate::types::{DiscussionLink, GovernanceConfig, GovernanceError, Proposal, ProposalAction, ProposalState, VotingStrategy};
 use crate::voting_system::VotingSystemClient;
-use soroban_sdk::{contract, contractimpl, panic_with_error, symbol_short, Address, Bytes, Env, String, Vec};
+use soroban_sdk::{contract, contractimpl, panic_with_error, symbol_short, Address, Bytes, BytesN, Env, String, Vec};
+
+/// Maximum length of a linked discussion URI
+const MAX_DISCUSSION_URI_LEN: u32 = 256;
 
 /// Proposal Manager Contract - manages the full proposal lifecycle
 /// @notice Follows Compound governance patterns with timelock integration
@@ -26,6 +30,7 @@ impl ProposalManager {
         config: GovernanceConfig,
     ) {
         admin.require_auth();
+        Self::_validate_config(&env, &config);
 
         env.storage().instance().set(&symbol_short!("ADMIN"), &admin);
         env.storage().instance().set(&symbol_short!("TOKEN"), &token_contract);
@@ -98,6 +103,7 @@ impl ProposalManager {
         };
 
         env.storage().persistent().set(&(symbol_short!("PROP"), proposal_id), &proposal);
+        env.storage().persistent().set(&(symbol_short!("PSTRAT"), proposal_id), &Self::get_voting_strategy(env.clone()));
 
         // Emit ProposalCreated event
         env.events().publish(
@@ -108,6 +114,40 @@ impl ProposalManager {
         proposal_id
     }
 
+    /// Create a proposal from typed actions
+    /// @notice Each action is encoded by `build_action`, so targets/calldatas are always well-formed
+    /// @param proposer: Address creating the proposal
+    /// @param actions: Typed actions to execute (config update, treasury transfer, contract upgrade)
+    /// @param description: Human-readable description
+    /// @return proposal_id: Unique ID for the created proposal
+    pub fn propose_actions(
+        env: Env,
+        proposer: Address,
+        actions: Vec<ProposalAction>,
+        description: String,
+    ) -> u64 {
+        let mut targets = Vec::new(&env);
+        let mut values = Vec::new(&env);
+        let mut calldatas = Vec::new(&env);
+
+        for action in actions.iter() {
+            let (target, value, calldata) = Self::build_action(env.clone(), action);
+            targets.push_back(target);
+            values.push_back(value);
+            calldatas.push_back(calldata);
+        }
+
+        Self::propose(env, proposer, targets, values, calldatas, description)
+    }
+
+    /// Encode a typed action into its `(target, value, calldata)` proposal entry
+    /// @notice Calls are executed by the timelock, which is also the treasury and the caller
+    /// passed to admin-gated targets
+    pub fn build_action(env: Env, action: ProposalAction) -> (Address, u128, Bytes) {
+        let timelock = Self::_timelock(&env);
+        proposal_actions::build_action(&env, &env.current_contract_address(), &timelock, &action)
+    }
+
     /// Get proposal state
     /// @notice Follows Compound's state machine: Pending -> Active -> Succeeded/Defeated -> Queued -> Executed
     pub fn state(env: Env, proposal_id: u64) -> ProposalState {
@@ -137,13 +177,21 @@ impl ProposalManager {
         let voting = Self::_voting(&env);
         
         let voting_client = VotingSystemClient::new(&env, &voting);
-        let succeeded = voting_client.proposal_succeeded(
-            &token,
-            &proposal.for_votes,
-            &proposal.against_votes,
-            &proposal.abstain_votes,
-            &config.quorum_numerator
-        );
+        let succeeded = match Self::_proposal_strategy(&env, proposal_id) {
+            VotingStrategy::Linear => voting_client.proposal_succeeded(
+                &token,
+                &proposal.for_votes,
+                &proposal.against_votes,
+                &proposal.abstain_votes,
+                &config.quorum_numerator
+            ),
+            // Quadratic tallies are not in token units, so quorum is checked on raw turnout
+            VotingStrategy::Quadratic => {
+                let turnout = Self::get_turnout(env.clone(), proposal_id);
+                voting_client.quorum_reached(&token, &turnout, &0, &0, &config.quorum_numerator)
+                    && proposal.for_votes > proposal.against_votes
+            }
+        };
 
         if !succeeded {
             return ProposalState::Defeated;
@@ -165,6 +213,7 @@ impl ProposalManager {
     /// Queue a successful proposal for execution (after timelock)
     /// @notice Only succeeded proposals can be queued
     pub fn queue(env: Env, proposal_id: u64) -> u64 {
+
         let state = Self::state(env.clone(), proposal_id);
         if state != ProposalState::Succeeded {
             panic_with_error!(&env, GovernanceError::ProposalNotSucceeded);
@@ -199,6 +248,7 @@ impl ProposalManager {
     /// Execute a queued proposal
     /// @notice Only queued proposals past their ETA can be executed
     pub fn execute(env: Env, proposal_id: u64) {
+
         let state = Self::state(env.clone(), proposal_id);
         if state != ProposalState::Queued {
             panic_with_error!(&env, GovernanceError::InvalidState);
@@ -211,9 +261,14 @@ impl ProposalManager {
             panic_with_error!(&env, GovernanceError::TimelockNotExpired);
         }
 
+        // Mark executed before any external call so a callback can never execute it again
+        proposal.executed = true;
+        env.storage().persistent().set(&(symbol_short!("PROP"), proposal_id), &proposal);
+
         let timelock = Self::_timelock(&env);
         let timelock_client = TimelockClient::new(&env, &timelock);
 
+        // No lock needed while targets run: the host rejects any call back into this contract
         // Execute all transactions
         for i in 0..proposal.targets.len() {
             let target = proposal.targets.get(i).unwrap();
@@ -224,9 +279,6 @@ impl ProposalManager {
             timelock_client.execute_transaction(&target, &value, &data, &proposal.eta);
         }
 
-        proposal.executed = true;
-        env.storage().persistent().set(&(symbol_short!("PROP"), proposal_id), &proposal);
-
         // Emit ProposalExecuted event
         env.events().publish((symbol_short!("PropExec"), proposal_id), env.ledger().timestamp());
     }
@@ -275,6 +327,49 @@ impl ProposalManager {
         Self::_get_proposal(&env, proposal_id)
     }
 
+    /// Link a proposal to its off-chain discussion thread
+    /// @notice Only the proposer or admin can link, and a proposal is linked at most once so the
+    /// pointer stays auditable
+    /// @param caller: The proposer or admin
+    /// @param proposal_id: The proposal ID
+    /// @param url_hash: sha256 of the canonical discussion URL
+    /// @param uri: The discussion URL
+    pub fn link_discussion(env: Env, caller: Address, proposal_id: u64, url_hash: BytesN<32>, uri: String) {
+        caller.require_auth();
+
+        let proposal = Self::_get_proposal(&env, proposal_id);
+        if caller != proposal.proposer && caller != Self::_admin(&env) {
+            panic_with_error!(&env, GovernanceError::Unauthorized);
+        }
+
+        if uri.is_empty() || uri.len() > MAX_DISCUSSION_URI_LEN {
+            panic_with_error!(&env, GovernanceError::InvalidProposal);
+        }
+
+        let key = (symbol_short!("DISCUSS"), proposal_id);
+        if env.storage().persistent().has(&key) {
+            panic_with_error!(&env, GovernanceError::InvalidState);
+        }
+
+        let link = DiscussionLink {
+            url_hash: url_hash.clone(),
+            uri: uri.clone(),
+            linked_by: caller.clone(),
+            linked_at: env.ledger().sequence() as u64,
+        };
+        env.storage().persistent().set(&key, &link);
+
+        // Emit DiscussionLinked event
+        env.events().publish((symbol_short!("DiscLink"), proposal_id), (url_hash, uri, caller));
+    }
+
+    /// Get the discussion linked to a proposal, if any
+    pub fn get_discussion(env: Env, proposal_id: u64) -> Option<DiscussionLink> {
+        env.storage()
+            .persistent()
+            .get(&(symbol_short!("DISCUSS"), proposal_id))
+    }
+
     /// Get total number of proposals
     pub fn proposal_count(env: Env) -> u64 {
         env.storage()
@@ -288,13 +383,42 @@ impl ProposalManager {
         Self::_config(&env)
     }
 
-    /// Record a vote for a proposal (called by voting system or externally)
+    /// Record a vote for a proposal
+    /// @notice Only the stored voting contract may call this (see `VotingSystem::submit_vote`),
+    /// and only while the proposal is Active. The weight is not trusted: it must equal the
+    /// voter's power on this manager's token at the proposal's start block.
     /// @param proposal_id: The proposal ID
+    /// @param voter: The voter whose receipt is counted (once per proposal)
     /// @param support: Vote type
-    /// @param votes: Number of votes
-    pub fn record_vote(env: Env, proposal_id: u64, support: crate::types::VoteSupport, votes: u128) {
+    /// @param votes: Voting power of the voter; weighted by the proposal's voting strategy
+    pub fn record_vote(env: Env, proposal_id: u64, voter: Address, support: crate::types::VoteSupport, votes: u128) {
+        Self::_voting(&env).require_auth();
+
         let mut proposal = Self::_get_proposal(&env, proposal_id);
 
+        // Tallies are immutable outside the voting window
+        let current_block = env.ledger().sequence() as u64;
+        if current_block < proposal.start_block || current_block > proposal.end_block {
+            panic_with_error!(&env, GovernanceError::ProposalNotActive);
+        }
+
+        let voted_key = (symbol_short!("VOTED"), proposal_id, voter.clone());
+        if env.storage().persistent().has(&voted_key) {
+            panic_with_error!(&env, GovernanceError::AlreadyVoted);
+        }
+
+        // Receipts taken against another token or snapshot block are rejected
+        let token_client = GovernanceTokenClient::new(&env, &Self::_token(&env));
+        if votes != token_client.get_prior_votes(&voter, &proposal.start_block) {
+            panic_with_error!(&env, GovernanceError::InvalidCheckpoint);
+        }
+        env.storage().persistent().set(&voted_key, &votes);
+
+        let turnout = Self::get_turnout(env.clone(), proposal_id).checked_add(votes)
+            .expect("Overflow in turnout");
+        env.storage().persistent().set(&(symbol_short!("TURNOUT"), proposal_id), &turnout);
+        let votes = Self::_vote_weight(Self::_proposal_strategy(&env, proposal_id), votes);
+
         match support {
             crate::types::VoteSupport::For => {
                 proposal.for_votes = proposal.for_votes.checked_add(votes)
@@ -313,21 +437,144 @@ impl ProposalManager {
         env.storage().persistent().set(&(symbol_short!("PROP"), proposal_id), &proposal);
     }
 
+    /// Get the voting power a voter contributed to a proposal's tally (0 if not counted)
+    pub fn get_counted_votes(env: Env, proposal_id: u64, voter: Address) -> u128 {
+        env.storage()
+            .persistent()
+            .get(&(symbol_short!("VOTED"), proposal_id, voter))
+            .unwrap_or(0)
+    }
+
+    /// Get the raw voting power of all votes counted on a proposal, used for quorum
+    /// @notice Proposals created before turnout was tracked were all linear, so their tallies
+    /// already are raw voting power
+    pub fn get_turnout(env: Env, proposal_id: u64) -> u128 {
+        if let Some(turnout) = env.storage().persistent().get(&(symbol_short!("TURNOUT"), proposal_id)) {
+            return turnout;
+        }
+        let proposal = Self::_get_proposal(&env, proposal_id);
+        proposal.for_votes + proposal.against_votes + proposal.abstain_votes
+    }
+
+    /// Get the voting strategy a proposal is tallied with (fixed at creation)
+    pub fn get_proposal_strategy(env: Env, proposal_id: u64) -> VotingStrategy {
+        Self::_get_proposal(&env, proposal_id);
+        Self::_proposal_strategy(&env, proposal_id)
+    }
+
+    /// Get the voting strategy applied to new proposals
+    pub fn get_voting_strategy(env: Env) -> VotingStrategy {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("STRATEGY"))
+            .unwrap_or(VotingStrategy::Linear)
+    }
+
+    /// Set the voting strategy applied to proposals created from now on
+    /// @notice Only admin or the timelock (through a passed proposal) can update. Kept apart
+    /// from `GovernanceConfig` so configs stored before strategies existed still decode.
+    pub fn set_voting_strategy(env: Env, caller: Address, strategy: VotingStrategy) {
+        caller.require_auth();
+
+        if caller != Self::_admin(&env) && caller != Self::_timelock(&env) {
+            panic_with_error!(&env, GovernanceError::Unauthorized);
+        }
+
+        env.storage().instance().set(&symbol_short!("STRATEGY"), &strategy);
+
+        env.events().publish((symbol_short!("StratUpd"),), strategy);
+    }
+
     /// Update governance configuration
-    /// @notice Only admin can update 
+    /// @notice Only admin or the timelock (through a passed proposal) can update
     pub fn update_config(env: Env, admin: Address, new_config: GovernanceConfig) {
         admin.require_auth();
         
-        let stored_admin = Self::_admin(&env);
-        if admin != stored_admin {
+        if admin != Self::_admin(&env) && admin != Self::_timelock(&env) {
             panic_with_error!(&env, GovernanceError::Unauthorized);
         }
+        Self::_validate_config(&env, &new_config);
 
         env.storage().instance().set(&symbol_short!("CONFIG"), &new_config);
         
         env.events().publish((symbol_short!("CfgUpdt"),), new_config);
     }
 
+    /// Get the voting contract allowed to record votes
+    pub fn get_voting_contract(env: Env) -> Address {
+        Self::_voting(&env)
+    }
+
+    /// Get the timelock contract
+    pub fn get_timelock(env: Env) -> Address {
+        Self::_timelock(&env)
+    }
+
+    /// Replace the voting contract allowed to record votes
+    /// @notice Callable by the admin or by the timelock (i.e. through a passed proposal)
+    pub fn set_voting_contract(env: Env, caller: Address, voting_contract: Address) {
+        caller.require_auth();
+
+        if caller != Self::_admin(&env) && caller != Self::_timelock(&env) {
+            panic_with_error!(&env, GovernanceError::Unauthorized);
+        }
+
+        let old_voting = Self::_voting(&env);
+        env.storage().instance().set(&symbol_short!("VOTING"), &voting_contract);
+
+        // Emit VotingContractUpdated event
+        env.events().publish((symbol_short!("VotCtrUp"),), (old_voting, voting_contract));
+    }
+
+    /// Move a queued proposal's timelock entries to the current tx hash scheme
+    /// @notice Permissionless: the targets come from the stored proposal, so the caller can't
+    /// redirect a legacy entry to a different target
+    /// @return migrated: Number of transactions migrated
+    pub fn migrate_timelock_queue(env: Env, proposal_id: u64) -> u32 {
+        let proposal = Self::_get_proposal(&env, proposal_id);
+        if proposal.eta == 0 || proposal.canceled || proposal.executed {
+            panic_with_error!(&env, GovernanceError::InvalidState);
+        }
+
+        let timelock_client = TimelockClient::new(&env, &Self::_timelock(&env));
+        let mut migrated = 0u32;
+        for i in 0..proposal.targets.len() {
+            let target = proposal.targets.get(i).unwrap();
+            let value = proposal.values.get(i).unwrap();
+            let data = proposal.calldatas.get(i).unwrap();
+
+            if timelock_client.migrate_queued(&target, &value, &data, &proposal.eta) {
+                migrated += 1;
+            }
+        }
+        migrated
+    }
+
+    /// Set the timelock guardian allowed to trigger `emergency_pause`
+    /// @notice Only admin can call; the timelock accepts this contract as its admin
+    pub fn set_timelock_guardian(env: Env, caller: Address, guardian: Address) {
+        caller.require_auth();
+
+        if caller != Self::_admin(&env) {
+            panic_with_error!(&env, GovernanceError::Unauthorized);
+        }
+
+        TimelockClient::new(&env, &Self::_timelock(&env)).set_guardian(&guardian);
+    }
+
+    /// Re-enable the timelock after an emergency pause
+    /// @notice Only admin can call. The timelock itself enforces the cool-down, and proposals
+    /// whose transactions were invalidated must be proposed again.
+    pub fn unpause_timelock(env: Env, caller: Address) {
+        caller.require_auth();
+
+        if caller != Self::_admin(&env) {
+            panic_with_error!(&env, GovernanceError::Unauthorized);
+        }
+
+        TimelockClient::new(&env, &Self::_timelock(&env)).unpause();
+    }
+
     // ========== INTERNAL HELPER FUNCTIONS ==========
 
     fn _admin(env: &Env) -> Address {
@@ -358,6 +605,24 @@ impl ProposalManager {
             .unwrap()
     }
 
+    /// Tally weight of a voter's power
+    /// @notice Quadratic weight is taken per address, so splitting a balance across accounts
+    /// raises its total weight - only meaningful where voters are identity-checked off-chain
+    fn _vote_weight(strategy: VotingStrategy, votes: u128) -> u128 {
+        match strategy {
+            VotingStrategy::Linear => votes,
+            VotingStrategy::Quadratic => votes.isqrt(),
+        }
+    }
+
+    /// Proposals created before strategies existed have no entry and are linear
+    fn _proposal_strategy(env: &Env, proposal_id: u64) -> VotingStrategy {
+        env.storage()
+            .persistent()
+            .get(&(symbol_short!("PSTRAT"), proposal_id))
+            .unwrap_or(VotingStrategy::Linear)
+    }
+
     fn _config(env: &Env) -> GovernanceConfig {
         env.storage()
             .instance()
@@ -365,6 +630,14 @@ impl ProposalManager {
             .unwrap()
     }
 
+    /// Snapshots must stay inside the token's guaranteed checkpoint retention
+    fn _validate_config(env: &Env, config: &GovernanceConfig) {
+        let voting_window = config.voting_delay.saturating_add(config.voting_period);
+        if voting_window > MIN_RETAIN_LEDGERS {
+            panic_with_error!(env, GovernanceError::InvalidState);
+        }
+    }
+
     fn _get_proposal(env: &Env, proposal_id: u64) -> Proposal {
         env.storage()
             .persistent()
@@ -390,6 +663,7 @@ mod tests {
     use crate::timelock::{Timelock, TimelockClient};
     use crate::voting_system::{VotingSystem, VotingSystemClient};
     use crate::types::VoteSupport;
+    use soroban_sdk::contracttype;
     use soroban_sdk::testutils::{Address as _, Ledger};
 
     #[test]
@@ -447,7 +721,7 @@ mod tests {
             voting_period: 100,    // 100 blocks voting
             proposal_threshold: 100_000, // Need 100k tokens to propose
             quorum_numerator: 40,  // 40% quorum
-            timelock_delay: 172800, // 2 days
+            timelock_delay: 172800, // 2 days,
         };
         proposal_client.initialize(&admin, &token_id, &timelock_id, &voting_id, &config);
 
@@ -472,11 +746,11 @@ mod tests {
         let snapshot_block = proposal.start_block;
 
         // Cast votes and record them
-        let vote1 = voting_client.cast_vote(&token_id, &prop_id, &snapshot_block, &voter1, &VoteSupport::For);
-        proposal_client.record_vote(&prop_id, &VoteSupport::For, &vote1.votes);
+        voting_client.cast_vote(&token_id, &prop_id, &snapshot_block, &voter1, &VoteSupport::For);
+        voting_client.submit_vote(&proposal_id, &prop_id, &voter1);
         
-        let vote2 = voting_client.cast_vote(&token_id, &prop_id, &snapshot_block, &voter2, &VoteSupport::For);
-        proposal_client.record_vote(&prop_id, &VoteSupport::For, &vote2.votes);
+        voting_client.cast_vote(&token_id, &prop_id, &snapshot_block, &voter2, &VoteSupport::For);
+        voting_client.submit_vote(&proposal_id, &prop_id, &voter2);
 
         // Move past voting period
         env.ledger().with_mut(|li| li.sequence_number = 1120);
@@ -540,5 +814,536 @@ mod tests {
         // This should panic
         proposal_client.propose(&poor_proposer, &targets, &values, &calldatas, &String::from_str(&env, "Test"));
     }
-}
 
+    /// Deploy the full system and return a proposal that is open for voting
+    fn setup_active_proposal(env: &Env) -> (
+        Address,
+        Address,
+        Address,
+        VotingSystemClient<'_>,
+        ProposalManagerClient<'_>,
+        u64,
+    ) {
+        env.ledger().with_mut(|li| {
+            li.sequence_number = 1000;
+            li.timestamp = 1000000;
+        });
+
+        let token_id = env.register_contract(None, GovernanceToken);
+        let token_client = GovernanceTokenClient::new(env, &token_id);
+        let timelock_id = env.register_contract(None, Timelock);
+        let voting_id = env.register_contract(None, VotingSystem);
+        let voting_client = VotingSystemClient::new(env, &voting_id);
+        let proposal_id = env.register_contract(None, ProposalManager);
+        let proposal_client = ProposalManagerClient::new(env, &proposal_id);
+
+        let admin = Address::generate(env);
+        let voter = Address::generate(env);
+
+        env.mock_all_auths();
+
+        token_client.initialize(
+            &admin,
+            &String::from_str(env, "Gov"),
+            &String::from_str(env, "GOV"),
+            &18,
+            &1_000_000u128
+        );
+        token_client.transfer(&admin, &voter, &500_000u128);
+
+        env.ledger().with_mut(|li| li.sequence_number = 1005);
+
+        let config = GovernanceConfig {
+            voting_delay: 10,
+            voting_period: 100,
+            proposal_threshold: 100_000,
+            quorum_numerator: 40,
+            timelock_delay: 172800,
+        };
+        proposal_client.initialize(&admin, &token_id, &timelock_id, &voting_id, &config);
+
+        let prop_id = proposal_client.propose(
+            &admin,
+            &Vec::from_array(env, [Address::generate(env)]),
+            &Vec::from_array(env, [0u128]),
+            &Vec::from_array(env, [Bytes::new(env)]),
+            &String::from_str(env, "Test")
+        );
+
+        env.ledger().with_mut(|li| li.sequence_number = 1016);
+
+        (admin, token_id, voter, voting_client, proposal_client, prop_id)
+    }
+
+    #[test]
+    fn test_quadratic_voting_tally() {
+        let env = Env::default();
+        env.ledger().with_mut(|li| {
+            li.sequence_number = 1000;
+            li.timestamp = 1000000;
+        });
+
+        let token_id = env.register_contract(None, GovernanceToken);
+        let token_client = GovernanceTokenClient::new(&env, &token_id);
+        let timelock_id = env.register_contract(None, Timelock);
+        let voting_id = env.register_contract(None, VotingSystem);
+        let voting_client = VotingSystemClient::new(&env, &voting_id);
+        let proposal_id = env.register_contract(None, ProposalManager);
+        let proposal_client = ProposalManagerClient::new(&env, &proposal_id);
+
+        let admin = Address::generate(&env);
+        let whale = Address::generate(&env);
+
+        env.mock_all_auths();
+
+        token_client.initialize(
+            &admin,
+            &String::from_str(&env, "Gov"),
+            &String::from_str(&env, "GOV"),
+            &18,
+            &1_000_000u128
+        );
+        token_client.transfer(&admin, &whale, &250_000u128);
+        let mut small_holders = Vec::new(&env);
+        for _ in 0..6 {
+            let holder = Address::generate(&env);
+            token_client.transfer(&admin, &holder, &40_000u128);
+            small_holders.push_back(holder);
+        }
+
+        env.ledger().with_mut(|li| li.sequence_number = 1005);
+
+        let config = GovernanceConfig {
+            voting_delay: 10,
+            voting_period: 100,
+            proposal_threshold: 100_000,
+            quorum_numerator: 40,
+            timelock_delay: 172800,
+        };
+        proposal_client.initialize(&admin, &token_id, &timelock_id, &voting_id, &config);
+        proposal_client.set_voting_strategy(&admin, &VotingStrategy::Quadratic);
+
+        let prop_id = proposal_client.propose(
+            &admin,
+            &Vec::from_array(&env, [Address::generate(&env)]),
+            &Vec::from_array(&env, [0u128]),
+            &Vec::from_array(&env, [Bytes::new(&env)]),
+            &String::from_str(&env, "Quadratic")
+        );
+
+        // The strategy is fixed when the proposal is created
+        proposal_client.set_voting_strategy(&admin, &VotingStrategy::Linear);
+        assert_eq!(proposal_client.get_proposal_strategy(&prop_id), VotingStrategy::Quadratic);
+
+        env.ledger().with_mut(|li| li.sequence_number = 1016);
+        let snapshot_block = proposal_client.get_proposal(&prop_id).start_block;
+
+        voting_client.cast_vote(&token_id, &prop_id, &snapshot_block, &whale, &VoteSupport::Against);
+        voting_client.submit_vote(&proposal_id, &prop_id, &whale);
+        for holder in small_holders.iter() {
+            voting_client.cast_vote(&token_id, &prop_id, &snapshot_block, &holder, &VoteSupport::For);
+            voting_client.submit_vote(&proposal_id, &prop_id, &holder);
+        }
+
+        // sqrt(250_000) = 500 against, 6 * sqrt(40_000) = 1_200 for
+        let proposal = proposal_client.get_proposal(&prop_id);
+        assert_eq!(proposal.against_votes, 500);
+        assert_eq!(proposal.for_votes, 1_200);
+        assert_eq!(proposal_client.get_turnout(&prop_id), 490_000);
+
+        // Quorum (40% of 1M) is met on raw turnout; the minority whale is outvoted
+        env.ledger().with_mut(|li| li.sequence_number = 1120);
+        assert_eq!(proposal_client.state(&prop_id), ProposalState::Succeeded);
+    }
+
+    #[test]
+    fn test_quadratic_voting_is_not_sybil_resistant() {
+        let env = Env::default();
+        env.ledger().with_mut(|li| {
+            li.sequence_number = 1000;
+            li.timestamp = 1000000;
+        });
+
+        let token_id = env.register_contract(None, GovernanceToken);
+        let token_client = GovernanceTokenClient::new(&env, &token_id);
+        let timelock_id = env.register_contract(None, Timelock);
+        let voting_id = env.register_contract(None, VotingSystem);
+        let voting_client = VotingSystemClient::new(&env, &voting_id);
+        let proposal_id = env.register_contract(None, ProposalManager);
+        let proposal_client = ProposalManagerClient::new(&env, &proposal_id);
+
+        let admin = Address::generate(&env);
+        let holder = Address::generate(&env);
+
+        env.mock_all_auths();
+
+        token_client.initialize(
+            &admin,
+            &String::from_str(&env, "Gov"),
+            &String::from_str(&env, "GOV"),
+            &18,
+            &1_000_000u128
+        );
+        // One holder keeps 250k, another spreads the same 250k over 25 accounts
+        token_client.transfer(&admin, &holder, &250_000u128);
+        let mut sybils = Vec::new(&env);
+        for _ in 0..25 {
+            let sybil = Address::generate(&env);
+            token_client.transfer(&admin, &sybil, &10_000u128);
+            sybils.push_back(sybil);
+        }
+
+        env.ledger().with_mut(|li| li.sequence_number = 1005);
+
+        let config = GovernanceConfig {
+            voting_delay: 10,
+            voting_period: 100,
+            proposal_threshold: 100_000,
+            quorum_numerator: 40,
+            timelock_delay: 172800,
+        };
+        proposal_client.initialize(&admin, &token_id, &timelock_id, &voting_id, &config);
+        proposal_client.set_voting_strategy(&admin, &VotingStrategy::Quadratic);
+
+        let prop_id = proposal_client.propose(
+            &admin,
+            &Vec::from_array(&env, [Address::generate(&env)]),
+            &Vec::from_array(&env, [0u128]),
+            &Vec::from_array(&env, [Bytes::new(&env)]),
+            &String::from_str(&env, "Split")
+        );
+
+        env.ledger().with_mut(|li| li.sequence_number = 1016);
+        let snapshot_block = proposal_client.get_proposal(&prop_id).start_block;
+
+        voting_client.cast_vote(&token_id, &prop_id, &snapshot_block, &holder, &VoteSupport::Against);
+        voting_client.submit_vote(&proposal_id, &prop_id, &holder);
+        for sybil in sybils.iter() {
+            voting_client.cast_vote(&token_id, &prop_id, &snapshot_block, &sybil, &VoteSupport::For);
+            voting_client.submit_vote(&proposal_id, &prop_id, &sybil);
+        }
+
+        // Same raw power, but sqrt is per address: sqrt(250_000) = 500 vs 25 * sqrt(10_000) = 2_500
+        let proposal = proposal_client.get_proposal(&prop_id);
+        assert_eq!(proposal.against_votes, 500);
+        assert_eq!(proposal.for_votes, 2_500);
+        assert_eq!(proposal_client.get_turnout(&prop_id), 500_000);
+    }
+
+    /// `Proposal` as stored before voting strategies existed
+    #[contracttype]
+    #[derive(Clone, Debug)]
+    pub struct ProposalV0 {
+        pub id: u64,
+        pub proposer: Address,
+        pub targets: Vec<Address>,
+        pub values: Vec<u128>,
+        pub calldatas: Vec<Bytes>,
+        pub description: String,
+        pub start_block: u64,
+        pub end_block: u64,
+        pub for_votes: u128,
+        pub against_votes: u128,
+        pub abstain_votes: u128,
+        pub canceled: bool,
+        pub executed: bool,
+        pub eta: u64,
+    }
+
+    /// `GovernanceConfig` as stored before voting strategies existed
+    #[contracttype]
+    #[derive(Clone, Debug)]
+    pub struct GovernanceConfigV0 {
+        pub voting_delay: u64,
+        pub voting_period: u64,
+        pub proposal_threshold: u128,
+        pub quorum_numerator: u64,
+        pub timelock_delay: u64,
+    }
+
+    #[test]
+    fn test_pre_upgrade_proposal_decodes() {
+        let env = Env::default();
+        let (admin, _, _, _, proposal_client, _) = setup_active_proposal(&env);
+
+        let legacy = ProposalV0 {
+            id: 7,
+            proposer: admin.clone(),
+            targets: Vec::from_array(&env, [Address::generate(&env)]),
+            values: Vec::from_array(&env, [0u128]),
+            calldatas: Vec::from_array(&env, [Bytes::new(&env)]),
+            description: String::from_str(&env, "Before upgrade"),
+            start_block: 900,
+            end_block: 1000,
+            for_votes: 450_000,
+            against_votes: 50_000,
+            abstain_votes: 0,
+            canceled: false,
+            executed: false,
+            eta: 0,
+        };
+        let legacy_config = GovernanceConfigV0 {
+            voting_delay: 10,
+            voting_period: 100,
+            proposal_threshold: 100_000,
+            quorum_numerator: 40,
+            timelock_delay: 172800,
+        };
+        env.as_contract(&proposal_client.address, || {
+            env.storage().persistent().set(&(symbol_short!("PROP"), 7u64), &legacy);
+            env.storage().instance().set(&symbol_short!("CONFIG"), &legacy_config);
+            env.storage().instance().remove(&symbol_short!("STRATEGY"));
+        });
+
+        let proposal = proposal_client.get_proposal(&7);
+        assert_eq!(proposal.for_votes, 450_000);
+        assert_eq!(proposal.description, String::from_str(&env, "Before upgrade"));
+        assert_eq!(proposal_client.get_config().quorum_numerator, 40);
+
+        // No strategy or turnout entries: linear, with turnout taken from the tallies
+        assert_eq!(proposal_client.get_proposal_strategy(&7), VotingStrategy::Linear);
+        assert_eq!(proposal_client.get_turnout(&7), 500_000);
+        assert_eq!(proposal_client.state(&7), ProposalState::Succeeded);
+        assert_eq!(proposal_client.get_voting_strategy(), VotingStrategy::Linear);
+    }
+
+    #[test]
+    fn test_set_voting_strategy_requires_admin_or_timelock() {
+        let env = Env::default();
+        let (_, _, voter, _, proposal_client, _) = setup_active_proposal(&env);
+
+        assert_eq!(
+            proposal_client.try_set_voting_strategy(&voter, &VotingStrategy::Quadratic),
+            Err(Ok(GovernanceError::Unauthorized.into()))
+        );
+        assert_eq!(proposal_client.get_voting_strategy(), VotingStrategy::Linear);
+
+        proposal_client.set_voting_strategy(&proposal_client.get_timelock(), &VotingStrategy::Quadratic);
+        assert_eq!(proposal_client.get_voting_strategy(), VotingStrategy::Quadratic);
+    }
+
+    #[test]
+    fn test_record_vote_rejects_direct_calls() {
+        let env = Env::default();
+        let (_, token_id, voter, voting_client, proposal_client, prop_id) = setup_active_proposal(&env);
+
+        let snapshot_block = proposal_client.get_proposal(&prop_id).start_block;
+        voting_client.cast_vote(&token_id, &prop_id, &snapshot_block, &voter, &VoteSupport::For);
+
+        // No authorizations from here on
+        env.set_auths(&[]);
+
+        // A direct caller cannot inflate the tally
+        let result = proposal_client.try_record_vote(&prop_id, &voter, &VoteSupport::For, &500_000u128);
+        assert!(result.is_err());
+        assert_eq!(proposal_client.get_proposal(&prop_id).for_votes, 0);
+
+        // The voting contract authorizes its own call when relaying the receipt
+        voting_client.submit_vote(&proposal_client.address, &prop_id, &voter);
+        assert_eq!(proposal_client.get_proposal(&prop_id).for_votes, 500_000);
+        assert_eq!(proposal_client.get_counted_votes(&prop_id, &voter), 500_000);
+    }
+
+    #[test]
+    fn test_submit_vote_counts_once() {
+        let env = Env::default();
+        let (_, token_id, voter, voting_client, proposal_client, prop_id) = setup_active_proposal(&env);
+
+        let snapshot_block = proposal_client.get_proposal(&prop_id).start_block;
+        voting_client.cast_vote(&token_id, &prop_id, &snapshot_block, &voter, &VoteSupport::For);
+
+        voting_client.submit_vote(&proposal_client.address, &prop_id, &voter);
+        assert_eq!(
+            voting_client.try_submit_vote(&proposal_client.address, &prop_id, &voter),
+            Err(Ok(GovernanceError::AlreadyVoted.into()))
+        );
+    }
+
+    /// Token that reports any account as holding 10M votes
+    #[contract]
+    pub struct BogusToken;
+
+    #[contractimpl]
+    impl BogusToken {
+        pub fn get_prior_votes(_env: Env, _account: Address, _block_number: u64) -> u128 {
+            10_000_000
+        }
+    }
+
+    #[test]
+    fn test_vote_through_bogus_token_is_rejected() {
+        let env = Env::default();
+        let (_, token_id, voter, voting_client, proposal_client, prop_id) = setup_active_proposal(&env);
+        let bogus_id = env.register_contract(None, BogusToken);
+        let attacker = Address::generate(&env);
+
+        // The receipt claims the bogus token's power, but the manager recomputes it on its own token
+        let snapshot_block = proposal_client.get_proposal(&prop_id).start_block;
+        let receipt = voting_client.cast_vote(&bogus_id, &prop_id, &snapshot_block, &attacker, &VoteSupport::For);
+        assert_eq!(receipt.votes, 10_000_000);
+        assert_eq!(
+            voting_client.try_submit_vote(&proposal_client.address, &prop_id, &attacker),
+            Err(Ok(GovernanceError::InvalidCheckpoint.into()))
+        );
+
+        // A real holder's receipt taken at another block is rejected the same way
+        voting_client.cast_vote(&token_id, &prop_id, &(snapshot_block - 1), &voter, &VoteSupport::For);
+        let proposal = proposal_client.get_proposal(&prop_id);
+        assert_eq!(proposal.for_votes, 0);
+        assert_eq!(proposal_client.get_turnout(&prop_id), 0);
+        assert_eq!(proposal_client.get_counted_votes(&prop_id, &attacker), 0);
+    }
+
+    #[test]
+    fn test_config_voting_window_bounded_by_checkpoint_retention() {
+        let env = Env::default();
+        let (admin, _, _, _, proposal_client, _) = setup_active_proposal(&env);
+
+        let mut config = proposal_client.get_config();
+        config.voting_delay = 1;
+        config.voting_period = MIN_RETAIN_LEDGERS;
+        assert_eq!(
+            proposal_client.try_update_config(&admin, &config),
+            Err(Ok(GovernanceError::InvalidState.into()))
+        );
+
+        config.voting_period = MIN_RETAIN_LEDGERS - 1;
+        proposal_client.update_config(&admin, &config);
+        assert_eq!(proposal_client.get_config().voting_period, MIN_RETAIN_LEDGERS - 1);
+    }
+
+    #[test]
+    fn test_set_voting_contract() {
+        let env = Env::default();
+        let (admin, _, _, _, proposal_client, _) = setup_active_proposal(&env);
+
+        let new_voting = env.register_contract(None, VotingSystem);
+        proposal_client.set_voting_contract(&admin, &new_voting);
+        assert_eq!(proposal_client.get_voting_contract(), new_voting);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_set_voting_contract_unauthorized() {
+        let env = Env::default();
+        let (_, _, voter, _, proposal_client, _) = setup_active_proposal(&env);
+
+        proposal_client.set_voting_contract(&voter, &Address::generate(&env));
+    }
+
+    #[test]
+    fn test_propose_actions_builds_calldata() {
+        let env = Env::default();
+        let (admin, _, _, _, proposal_client, _) = setup_active_proposal(&env);
+
+        let token = Address::generate(&env);
+        let recipient = Address::generate(&env);
+        let new_config = GovernanceConfig {
+            voting_delay: 20,
+            voting_period: 200,
+            proposal_threshold: 50_000,
+            quorum_numerator: 25,
+            timelock_delay: 86400,
+        };
+        let actions = Vec::from_array(&env, [
+            ProposalAction::UpdateConfig(new_config),
+            ProposalAction::TreasuryTransfer(crate::types::TreasuryTransfer {
+                token: token.clone(),
+                recipient,
+                amount: 1_000,
+            }),
+        ]);
+
+        let prop_id = proposal_client.propose_actions(&admin, &actions, &String::from_str(&env, "Typed"));
+        let proposal = proposal_client.get_proposal(&prop_id);
+
+        assert_eq!(proposal.targets.len(), 2);
+        assert_eq!(proposal.targets.get(0).unwrap(), proposal_client.address);
+        assert_eq!(proposal.targets.get(1).unwrap(), token);
+        assert_eq!(proposal.values, Vec::from_array(&env, [0u128, 0u128]));
+
+        let (function, _) = proposal_actions::decode_call(&env, &proposal.calldatas.get(0).unwrap());
+        assert_eq!(function, soroban_sdk::Symbol::new(&env, "update_config"));
+        let (function, _) = proposal_actions::decode_call(&env, &proposal.calldatas.get(1).unwrap());
+        assert_eq!(function, symbol_short!("transfer"));
+    }
+
+    #[test]
+    fn test_timelock_can_update_config() {
+        let env = Env::default();
+        let (_, _, _, _, proposal_client, _) = setup_active_proposal(&env);
+
+        let timelock = env.as_contract(&proposal_client.address, || {
+            env.storage().instance().get::<_, Address>(&symbol_short!("TIMELOCK")).unwrap()
+        });
+        let mut config = proposal_client.get_config();
+        config.quorum_numerator = 10;
+
+        proposal_client.update_config(&timelock, &config);
+        assert_eq!(proposal_client.get_config().quorum_numerator, 10);
+    }
+
+    #[test]
+    fn test_link_discussion() {
+        let env = Env::default();
+        let (admin, _, _, _, proposal_client, prop_id) = setup_active_proposal(&env);
+
+        assert_eq!(proposal_client.get_discussion(&prop_id), None);
+
+        let uri = String::from_str(&env, "https://forum.chenaikit.org/t/proposal-1");
+        let url_hash = BytesN::from_array(&env, &[9u8; 32]);
+        proposal_client.link_discussion(&admin, &prop_id, &url_hash, &uri);
+
+        let link = proposal_client.get_discussion(&prop_id).unwrap();
+        assert_eq!(link.url_hash, url_hash);
+        assert_eq!(link.uri, uri);
+        assert_eq!(link.linked_by, admin);
+        assert_eq!(link.linked_at, 1016);
+
+        // The pointer cannot be swapped afterwards
+        let result = proposal_client.try_link_discussion(
+            &admin,
+            &prop_id,
+            &BytesN::from_array(&env, &[1u8; 32]),
+            &String::from_str(&env, "https://elsewhere.example/t/1"),
+        );
+        assert!(result.is_err());
+        assert_eq!(proposal_client.get_discussion(&prop_id).unwrap().uri, uri);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_link_discussion_unauthorized() {
+        let env = Env::default();
+        let (_, _, voter, _, proposal_client, prop_id) = setup_active_proposal(&env);
+
+        proposal_client.link_discussion(
+            &voter,
+            &prop_id,
+            &BytesN::from_array(&env, &[9u8; 32]),
+            &String::from_str(&env, "https://forum.chenaikit.org/t/proposal-1"),
+        );
+    }
+
+    #[test]
+    fn test_governance_controls_timelock_pause() {
+        let env = Env::default();
+        let (admin, _, voter, _, proposal_client, _) = setup_active_proposal(&env);
+
+        let timelock_id = proposal_client.get_timelock();
+        let timelock_client = TimelockClient::new(&env, &timelock_id);
+        timelock_client.initialize(&proposal_client.address, &172800u64);
+
+        let guardian = Address::generate(&env);
+        proposal_client.set_timelock_guardian(&admin, &guardian);
+        assert_eq!(timelock_client.get_guardian(), Some(guardian.clone()));
+
+        timelock_client.emergency_pause(&guardian);
+        assert!(timelock_client.is_paused());
+
+        env.ledger().with_mut(|li| li.timestamp += crate::timelock::EMERGENCY_COOLDOWN);
+        assert!(proposal_client.try_unpause_timelock(&voter).is_err());
+        proposal_client.unpause_timelock(&admin);
+        assert!(!timelock_client.is_paused());
+    }
+}
@@ -0,0 +1,296 @@
+#![cfg(test)]
+//! Reentrancy and Invariant Tests for ProposalManager Execution
+//!
+//! Execution hands control to external code (the timelock, and through it the proposal
+//! targets). The Soroban host refuses any call back into a contract that is already on the
+//! call stack, so ProposalManager needs no lock of its own. These tests check that:
+//! - the host rejects a malicious timelock's callback into governance mid-execution
+//! - the host rejects a proposal target calling back into `execute` or `record_vote`
+//! - a proposal cannot be executed twice
+//! - tallies are immutable outside the voting window
+
+use crate::governance_token::{GovernanceToken, GovernanceTokenClient};
+use crate::proposal_manager::{ProposalManager, ProposalManagerClient};
+use crate::timelock::{Timelock, TimelockClient};
+use crate::types::{GovernanceConfig, GovernanceError, ProposalState, VoteSupport};
+use crate::voting_system::{VotingSystem, VotingSystemClient};
+use soroban_sdk::testutils::{Address as _, Ledger};
+use soroban_sdk::xdr::{ScErrorCode, ScErrorType};
+use soroban_sdk::{contract, contractimpl, symbol_short, Address, Bytes, Env, Error, String, Vec};
+
+/// Timelock stand-in that calls back into the ProposalManager while it executes
+#[contract]
+pub struct MaliciousTimelock;
+
+#[contractimpl]
+impl MaliciousTimelock {
+    /// Configure the callback performed during `execute_transaction`
+    pub fn arm(env: Env, governance: Address, proposal_id: u64) {
+        env.storage().instance().set(&symbol_short!("GOV"), &governance);
+        env.storage().instance().set(&symbol_short!("PROPID"), &proposal_id);
+    }
+
+    pub fn queue_transaction(env: Env, _target: Address, _value: u128, _data: Bytes, _eta: u64) -> Bytes {
+        Bytes::new(&env)
+    }
+
+    pub fn execute_transaction(env: Env, _target: Address, _value: u128, _data: Bytes, _eta: u64) -> Bytes {
+        let governance: Address = env.storage().instance().get(&symbol_short!("GOV")).unwrap();
+        let proposal_id: u64 = env.storage().instance().get(&symbol_short!("PROPID")).unwrap();
+
+        // Record whether the host refused the callback with its re-entry error
+        let client = ProposalManagerClient::new(&env, &governance);
+        let reentry_error = Error::from_type_and_code(ScErrorType::Context, ScErrorCode::InvalidAction);
+        let rejected = client.try_execute(&proposal_id) == Err(Ok(reentry_error));
+        env.storage().instance().set(&symbol_short!("REJECTED"), &rejected);
+
+        Bytes::new(&env)
+    }
+
+    pub fn cancel_transaction(_env: Env, _target: Address, _value: u128, _data: Bytes, _eta: u64) {}
+
+    /// Whether the host rejected the re-entrant call
+    pub fn reentry_rejected(env: Env) -> bool {
+        env.storage().instance().get(&symbol_short!("REJECTED")).unwrap_or(false)
+    }
+}
+
+/// Forwarding timelock and target live in their own module: their entry points
+/// share names with MaliciousTimelock's
+mod target_reentry {
+    use super::*;
+
+    /// Timelock stand-in that forwards each transaction to its target, as a live executor would
+    #[contract]
+    pub struct ForwardingTimelock;
+
+    #[contractimpl]
+    impl ForwardingTimelock {
+        pub fn queue_transaction(env: Env, _target: Address, _value: u128, _data: Bytes, _eta: u64) -> Bytes {
+            Bytes::new(&env)
+        }
+
+        pub fn execute_transaction(env: Env, target: Address, _value: u128, _data: Bytes, _eta: u64) -> Bytes {
+            ReentrantTargetClient::new(&env, &target).on_execute();
+            Bytes::new(&env)
+        }
+
+        pub fn cancel_transaction(_env: Env, _target: Address, _value: u128, _data: Bytes, _eta: u64) {}
+    }
+
+    /// Proposal target that tries to call back into governance when it is executed
+    #[contract]
+    pub struct ReentrantTarget;
+
+    #[contractimpl]
+    impl ReentrantTarget {
+        /// Configure the governance contract and proposal to call back into
+        pub fn arm(env: Env, governance: Address, proposal_id: u64, voter: Address) {
+            env.storage().instance().set(&symbol_short!("GOV"), &governance);
+            env.storage().instance().set(&symbol_short!("PROPID"), &proposal_id);
+            env.storage().instance().set(&symbol_short!("VOTER"), &voter);
+        }
+
+        pub fn on_execute(env: Env) {
+            let governance: Address = env.storage().instance().get(&symbol_short!("GOV")).unwrap();
+            let proposal_id: u64 = env.storage().instance().get(&symbol_short!("PROPID")).unwrap();
+            let voter: Address = env.storage().instance().get(&symbol_short!("VOTER")).unwrap();
+
+            let client = ProposalManagerClient::new(&env, &governance);
+            let reentry_error = Error::from_type_and_code(ScErrorType::Context, ScErrorCode::InvalidAction);
+            let execute_rejected = client.try_execute(&proposal_id) == Err(Ok(reentry_error));
+            let vote_rejected =
+                client.try_record_vote(&proposal_id, &voter, &VoteSupport::Against, &1u128) == Err(Ok(reentry_error));
+            env.storage().instance().set(&symbol_short!("REJECTED"), &(execute_rejected && vote_rejected));
+        }
+
+        /// Whether the host rejected both re-entrant calls
+        pub fn reentry_rejected(env: Env) -> bool {
+            env.storage().instance().get(&symbol_short!("REJECTED")).unwrap_or(false)
+        }
+    }
+}
+use target_reentry::{ForwardingTimelock, ReentrantTarget, ReentrantTargetClient};
+
+fn test_config() -> GovernanceConfig {
+    GovernanceConfig {
+        voting_delay: 10,
+        voting_period: 100,
+        proposal_threshold: 500_000,
+        quorum_numerator: 30,
+        timelock_delay: 7200,
+    }
+}
+
+/// Deploy token + voting + proposal manager wired to the given timelock and
+/// drive a proposal to the Queued state
+fn queued_proposal(env: &Env, timelock: &Address, real_timelock: bool) -> (Address, ProposalManagerClient<'static>, u64) {
+    queued_proposal_with_target(env, timelock, real_timelock, &Address::generate(env))
+}
+
+fn queued_proposal_with_target(
+    env: &Env,
+    timelock: &Address,
+    real_timelock: bool,
+    target: &Address,
+) -> (Address, ProposalManagerClient<'static>, u64) {
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 1000;
+        li.timestamp = 1_000_000;
+    });
+
+    let token_id = env.register_contract(None, GovernanceToken);
+    let token = GovernanceTokenClient::new(env, &token_id);
+    let voting_id = env.register_contract(None, VotingSystem);
+    let voting = VotingSystemClient::new(env, &voting_id);
+    let proposals_id = env.register_contract(None, ProposalManager);
+    let proposals = ProposalManagerClient::new(env, &proposals_id);
+
+    let admin = Address::generate(env);
+    let proposer = Address::generate(env);
+    let voter = Address::generate(env);
+
+    token.initialize(
+        &admin,
+        &String::from_str(env, "Gov"),
+        &String::from_str(env, "GOV"),
+        &18,
+        &10_000_000u128,
+    );
+    token.transfer(&admin, &proposer, &1_000_000u128);
+    token.transfer(&admin, &voter, &4_000_000u128);
+
+    env.ledger().with_mut(|li| li.sequence_number = 1005);
+    if real_timelock {
+        TimelockClient::new(env, timelock).initialize(&proposals_id, &7200u64);
+    }
+    proposals.initialize(&admin, &token_id, timelock, &voting_id, &test_config());
+
+    let prop_id = proposals.propose(
+        &proposer,
+        &Vec::from_array(env, [target.clone()]),
+        &Vec::from_array(env, [0u128]),
+        &Vec::from_array(env, [Bytes::new(env)]),
+        &String::from_str(env, "Reentrancy target"),
+    );
+
+    env.ledger().with_mut(|li| li.sequence_number = 1016);
+    let snapshot_block = proposals.get_proposal(&prop_id).start_block;
+    voting.cast_vote(&token_id, &prop_id, &snapshot_block, &voter, &VoteSupport::For);
+    voting.submit_vote(&proposals_id, &prop_id, &voter);
+
+    env.ledger().with_mut(|li| li.sequence_number = 1120);
+    let eta = proposals.queue(&prop_id);
+    env.ledger().with_mut(|li| li.timestamp = eta + 1);
+
+    (admin, proposals, prop_id)
+}
+
+#[test]
+fn test_host_rejects_reentrant_call() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let timelock_id = env.register_contract(None, MaliciousTimelock);
+    let timelock = MaliciousTimelockClient::new(&env, &timelock_id);
+    let (_, proposals, prop_id) = queued_proposal(&env, &timelock_id, false);
+
+    timelock.arm(&proposals.address, &prop_id);
+    proposals.execute(&prop_id);
+
+    // The callback never reaches ProposalManager code: the host refuses the re-entry itself
+    assert!(timelock.reentry_rejected());
+    assert_eq!(proposals.state(&prop_id), ProposalState::Executed);
+}
+
+#[test]
+fn test_host_rejects_target_reentry() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let timelock_id = env.register_contract(None, ForwardingTimelock);
+    let target_id = env.register_contract(None, ReentrantTarget);
+    let target = ReentrantTargetClient::new(&env, &target_id);
+    let (_, proposals, prop_id) = queued_proposal_with_target(&env, &timelock_id, false, &target_id);
+
+    target.arm(&proposals.address, &prop_id, &Address::generate(&env));
+    proposals.execute(&prop_id);
+
+    // Neither execute nor record_vote can be reached from the proposal's own target
+    assert!(target.reentry_rejected());
+    assert_eq!(proposals.state(&prop_id), ProposalState::Executed);
+}
+
+#[test]
+fn test_cannot_execute_twice() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let timelock_id = env.register_contract(None, Timelock);
+    let (_, proposals, prop_id) = queued_proposal(&env, &timelock_id, true);
+
+    proposals.execute(&prop_id);
+    assert_eq!(proposals.state(&prop_id), ProposalState::Executed);
+
+    assert_eq!(
+        proposals.try_execute(&prop_id),
+        Err(Ok(GovernanceError::InvalidState.into()))
+    );
+}
+
+#[test]
+fn test_tallies_immutable_after_end_block() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let timelock_id = env.register_contract(None, Timelock);
+    let (_, proposals, prop_id) = queued_proposal(&env, &timelock_id, true);
+
+    // Voting ended at block 1115; the tally can no longer move
+    assert_eq!(
+        proposals.try_record_vote(&prop_id, &Address::generate(&env), &VoteSupport::Against, &5_000_000u128),
+        Err(Ok(GovernanceError::ProposalNotActive.into()))
+    );
+}
+
+#[test]
+fn test_tallies_immutable_before_start_block() {
+    let env = Env::default();
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 1000;
+        li.timestamp = 1_000_000;
+    });
+    env.mock_all_auths();
+
+    let token_id = env.register_contract(None, GovernanceToken);
+    let token = GovernanceTokenClient::new(&env, &token_id);
+    let timelock_id = env.register_contract(None, Timelock);
+    let voting_id = env.register_contract(None, VotingSystem);
+    let proposals_id = env.register_contract(None, ProposalManager);
+    let proposals = ProposalManagerClient::new(&env, &proposals_id);
+
+    let admin = Address::generate(&env);
+    token.initialize(
+        &admin,
+        &String::from_str(&env, "Gov"),
+        &String::from_str(&env, "GOV"),
+        &18,
+        &10_000_000u128,
+    );
+    env.ledger().with_mut(|li| li.sequence_number = 1005);
+    proposals.initialize(&admin, &token_id, &timelock_id, &voting_id, &test_config());
+
+    let prop_id = proposals.propose(
+        &admin,
+        &Vec::from_array(&env, [Address::generate(&env)]),
+        &Vec::from_array(&env, [0u128]),
+        &Vec::from_array(&env, [Bytes::new(&env)]),
+        &String::from_str(&env, "Pending"),
+    );
+
+    // Still Pending - votes cannot be recorded yet
+    assert_eq!(
+        proposals.try_record_vote(&prop_id, &admin, &VoteSupport::For, &1u128),
+        Err(Ok(GovernanceError::ProposalNotActive.into()))
+    );
+}
@@ -1,6 +1,14 @@
 #![allow(unused)]
-use crate::types::GovernanceError;
-use soroban_sdk::{contract, contractimpl, panic_with_error, symbol_short, Address, Bytes, Env};
+use crate::proposal_actions;
+use crate::types::{GovernanceError, QueuedTransaction};
+use soroban_sdk::xdr::ToXdr;
+use soroban_sdk::{contract, contractimpl, panic_with_error, symbol_short, Address, Bytes, Env, TryFromVal, Val, Vec};
+
+/// Domain tag prefixed to every transaction hash preimage
+const TX_HASH_DOMAIN: &[u8] = b"chenaikit:timelock:tx:v2";
+
+/// Minimum time (seconds) the timelock stays paused after an emergency pause
+pub const EMERGENCY_COOLDOWN: u64 = 2 * 24 * 60 * 60; // 2 days
 
 /// Timelock Contract - delays execution of governance proposals
 /// @notice This provides a safety window for protocol defenders to react to malicious proposals
@@ -34,6 +42,7 @@ impl Timelock {
     ) -> Bytes {
         let admin = Self::_admin(&env);
         admin.require_auth();
+        Self::_require_not_paused(&env);
 
         let delay = Self::_delay(&env);
         let current_time = env.ledger().timestamp();
@@ -46,8 +55,13 @@ impl Timelock {
         // Create transaction hash
         let tx_hash = Self::_get_tx_hash(&env, &target, value, &data, eta);
 
-        // Mark as queued
-        env.storage().persistent().set(&(symbol_short!("QUEUED"), tx_hash.clone()), &true);
+        // Index for `list_queued` (re-queueing an identical transaction is a no-op)
+        if !Self::is_queued(env.clone(), tx_hash.clone()) {
+            Self::_index_add(&env, &tx_hash, &target, value, &data, eta);
+        }
+
+        // Mark as queued in the current epoch (an emergency pause bumps the epoch)
+        env.storage().persistent().set(&(symbol_short!("QUEUED"), tx_hash.clone()), &Self::_epoch(&env));
 
         // Emit QueueTransaction event
         env.events().publish(
@@ -69,6 +83,7 @@ impl Timelock {
     ) -> Bytes {
         let admin = Self::_admin(&env);
         admin.require_auth();
+        Self::_require_not_paused(&env);
 
         let tx_hash = Self::_get_tx_hash(&env, &target, value, &data, eta);
 
@@ -90,6 +105,7 @@ impl Timelock {
         }
 
         // Remove from queue
+        Self::_index_remove(&env, &tx_hash);
         env.storage().persistent().remove(&(symbol_short!("QUEUED"), tx_hash.clone()));
 
         // Execute the transaction
@@ -116,6 +132,9 @@ impl Timelock {
         admin.require_auth();
 
         let tx_hash = Self::_get_tx_hash(&env, &target, value, &data, eta);
+        if Self::is_queued(env.clone(), tx_hash.clone()) {
+            Self::_index_remove(&env, &tx_hash);
+        }
         env.storage().persistent().remove(&(symbol_short!("QUEUED"), tx_hash.clone()));
 
         // Emit CancelTransaction event
@@ -123,13 +142,199 @@ impl Timelock {
     }
 
     /// Check if a transaction is queued
+    /// @notice Transactions queued before the last emergency pause are no longer queued
     pub fn is_queued(env: Env, tx_hash: Bytes) -> bool {
         env.storage()
             .persistent()
-            .get::<_, bool>(&(symbol_short!("QUEUED"), tx_hash))
+            .get::<_, u32>(&(symbol_short!("QUEUED"), tx_hash))
+            .map(|epoch| epoch == Self::_epoch(&env))
             .unwrap_or(false)
     }
 
+    /// Re-key a transaction queued under the legacy hash scheme
+    /// @notice Only admin (governance contract) can call, since the legacy hash doesn't commit to
+    /// the target and only governance knows which target was actually queued. Entries voided by an
+    /// emergency pause are dropped rather than migrated.
+    /// @return migrated: Whether a live legacy entry was found and moved to the new hash
+    pub fn migrate_queued(env: Env, target: Address, value: u128, data: Bytes, eta: u64) -> bool {
+        let admin = Self::_admin(&env);
+        admin.require_auth();
+
+        let legacy_hash = Self::_get_legacy_tx_hash(&env, value, &data, eta);
+        let legacy_key = (symbol_short!("QUEUED"), legacy_hash.clone());
+        let stored: Option<Val> = env.storage().persistent().get(&legacy_key);
+        let stored = match stored {
+            Some(stored) => stored,
+            None => return false,
+        };
+
+        // Entries written before queue epochs existed hold `true`
+        let epoch = Self::_epoch(&env);
+        let live = match bool::try_from_val(&env, &stored) {
+            Ok(queued) => queued,
+            Err(_) => u32::try_from_val(&env, &stored).map(|e| e == epoch).unwrap_or(false),
+        };
+
+        if env.storage().persistent().has(&(symbol_short!("QPOS"), epoch, legacy_hash.clone())) {
+            Self::_index_remove(&env, &legacy_hash);
+        }
+        env.storage().persistent().remove(&legacy_key);
+        if !live {
+            return false;
+        }
+
+        let tx_hash = Self::_get_tx_hash(&env, &target, value, &data, eta);
+        if !Self::is_queued(env.clone(), tx_hash.clone()) {
+            Self::_index_add(&env, &tx_hash, &target, value, &data, eta);
+        }
+        env.storage().persistent().set(&(symbol_short!("QUEUED"), tx_hash.clone()), &epoch);
+
+        // Emit MigrateTransaction event
+        env.events().publish((symbol_short!("Migrate"), tx_hash), legacy_hash);
+
+        true
+    }
+
+    /// List pending transactions with decoded call summaries
+    /// @notice Order is not stable as transactions leave the queue (entries are swap-removed).
+    /// Transactions voided by an emergency pause are not listed.
+    /// @param offset: Index of the first transaction to return
+    /// @param limit: Maximum number of transactions to return
+    pub fn list_queued(env: Env, offset: u32, limit: u32) -> Vec<QueuedTransaction> {
+        let epoch = Self::_epoch(&env);
+        let count = Self::queued_count(env.clone());
+        let end = offset.saturating_add(limit).min(count);
+
+        let mut queued = Vec::new(&env);
+        for i in offset..end {
+            let tx_hash: Bytes = env.storage()
+                .persistent()
+                .get(&(symbol_short!("QIDX"), epoch, i))
+                .unwrap();
+            queued.push_back(
+                env.storage()
+                    .persistent()
+                    .get(&(symbol_short!("QTX"), epoch, tx_hash))
+                    .unwrap()
+            );
+        }
+        queued
+    }
+
+    /// Get the number of pending transactions
+    pub fn queued_count(env: Env) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&(symbol_short!("QCOUNT"), Self::_epoch(&env)))
+            .unwrap_or(0)
+    }
+
+    /// Delete index entries left behind by transactions voided in an emergency pause
+    /// @notice Anyone can call: only entries of an epoch that is already over are removed, at
+    /// most `limit` per call, so the cost of a large voided queue is spread across calls
+    /// @param epoch: The voided epoch to prune (must be older than the current one)
+    /// @param limit: Maximum number of transactions to remove
+    /// @return remaining: Entries still indexed under `epoch`
+    pub fn prune_voided(env: Env, epoch: u32, limit: u32) -> u32 {
+        if epoch >= Self::_epoch(&env) {
+            panic_with_error!(&env, GovernanceError::InvalidState);
+        }
+
+        let count_key = (symbol_short!("QCOUNT"), epoch);
+        let mut count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+        let stop = count.saturating_sub(limit);
+        while count > stop {
+            count -= 1;
+            let tx_hash: Bytes = env.storage()
+                .persistent()
+                .get(&(symbol_short!("QIDX"), epoch, count))
+                .unwrap();
+            env.storage().persistent().remove(&(symbol_short!("QIDX"), epoch, count));
+            env.storage().persistent().remove(&(symbol_short!("QPOS"), epoch, tx_hash.clone()));
+            env.storage().persistent().remove(&(symbol_short!("QTX"), epoch, tx_hash.clone()));
+
+            let queued_key = (symbol_short!("QUEUED"), tx_hash);
+            if env.storage().persistent().get::<_, u32>(&queued_key) == Some(epoch) {
+                env.storage().persistent().remove(&queued_key);
+            }
+        }
+
+        if count == 0 {
+            env.storage().persistent().remove(&count_key);
+        } else {
+            env.storage().persistent().set(&count_key, &count);
+        }
+        count
+    }
+
+    /// Set the guardian allowed to trigger an emergency pause
+    /// @notice Only admin (governance contract) can call this
+    pub fn set_guardian(env: Env, guardian: Address) {
+        let admin = Self::_admin(&env);
+        admin.require_auth();
+
+        env.storage().instance().set(&symbol_short!("GUARDIAN"), &guardian);
+
+        // Emit GuardianSet event
+        env.events().publish((symbol_short!("GuardSet"),), guardian);
+    }
+
+    /// Get the guardian address, if one is set
+    pub fn get_guardian(env: Env) -> Option<Address> {
+        env.storage().instance().get(&symbol_short!("GUARDIAN"))
+    }
+
+    /// Emergency circuit breaker
+    /// @notice Only the guardian can call this. Invalidates every queued transaction and blocks
+    /// queueing and execution until governance calls `unpause` after EMERGENCY_COOLDOWN.
+    /// @param guardian: The guardian address
+    pub fn emergency_pause(env: Env, guardian: Address) {
+        guardian.require_auth();
+
+        if Self::get_guardian(env.clone()) != Some(guardian.clone()) {
+            panic_with_error!(&env, GovernanceError::Unauthorized);
+        }
+        Self::_require_not_paused(&env);
+
+        // Bumping the epoch invalidates all queued transactions at once; the old epoch's index
+        // is left behind for `prune_voided`
+        let epoch = Self::_epoch(&env) + 1;
+        env.storage().instance().set(&symbol_short!("EPOCH"), &epoch);
+
+        let paused_at = env.ledger().timestamp();
+        env.storage().instance().set(&symbol_short!("PAUSEDAT"), &paused_at);
+
+        // Emit EmergencyPause event
+        env.events().publish((symbol_short!("EmrgPause"), guardian), (epoch, paused_at));
+    }
+
+    /// Re-enable the timelock after an emergency pause
+    /// @notice Only admin (governance contract) can call this, and only once the cool-down has
+    /// elapsed. Transactions invalidated by the pause must be re-queued.
+    pub fn unpause(env: Env) {
+        let admin = Self::_admin(&env);
+        admin.require_auth();
+
+        let paused_at: u64 = env.storage()
+            .instance()
+            .get(&symbol_short!("PAUSEDAT"))
+            .unwrap_or_else(|| panic_with_error!(&env, GovernanceError::InvalidState));
+
+        if env.ledger().timestamp() < paused_at + EMERGENCY_COOLDOWN {
+            panic_with_error!(&env, GovernanceError::CooldownActive);
+        }
+
+        env.storage().instance().remove(&symbol_short!("PAUSEDAT"));
+
+        // Emit Unpause event
+        env.events().publish((symbol_short!("Unpause"),), env.ledger().timestamp());
+    }
+
+    /// Check if the timelock is paused
+    pub fn is_paused(env: Env) -> bool {
+        env.storage().instance().has(&symbol_short!("PAUSEDAT"))
+    }
+
     /// Get the timelock delay
     pub fn get_delay(env: Env) -> u64 {
         Self::_delay(&env)
@@ -156,24 +361,100 @@ impl Timelock {
             .unwrap()
     }
 
+    fn _epoch(env: &Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("EPOCH"))
+            .unwrap_or(0)
+    }
+
+    /// Append a transaction to the current epoch's queue index
+    fn _index_add(env: &Env, tx_hash: &Bytes, target: &Address, value: u128, data: &Bytes, eta: u64) {
+        let (function, arg_count) = match proposal_actions::try_decode_call(env, data) {
+            Some((function, args)) => (Some(function), args.len()),
+            None => (None, 0),
+        };
+        let summary = QueuedTransaction {
+            tx_hash: tx_hash.clone(),
+            target: target.clone(),
+            value,
+            eta,
+            function,
+            arg_count,
+        };
+
+        let epoch = Self::_epoch(env);
+        let count = Self::queued_count(env.clone());
+        env.storage().persistent().set(&(symbol_short!("QTX"), epoch, tx_hash.clone()), &summary);
+        env.storage().persistent().set(&(symbol_short!("QIDX"), epoch, count), tx_hash);
+        env.storage().persistent().set(&(symbol_short!("QPOS"), epoch, tx_hash.clone()), &count);
+        env.storage().persistent().set(&(symbol_short!("QCOUNT"), epoch), &(count + 1));
+    }
+
+    /// Remove a transaction from the current epoch's queue index
+    /// @notice Swap-remove: the last entry takes the freed slot, so removal is O(1)
+    fn _index_remove(env: &Env, tx_hash: &Bytes) {
+        let epoch = Self::_epoch(env);
+        let position: u32 = env.storage()
+            .persistent()
+            .get(&(symbol_short!("QPOS"), epoch, tx_hash.clone()))
+            .unwrap();
+
+        let last = Self::queued_count(env.clone()) - 1;
+        if position != last {
+            let moved: Bytes = env.storage()
+                .persistent()
+                .get(&(symbol_short!("QIDX"), epoch, last))
+                .unwrap();
+            env.storage().persistent().set(&(symbol_short!("QIDX"), epoch, position), &moved);
+            env.storage().persistent().set(&(symbol_short!("QPOS"), epoch, moved), &position);
+        }
+        env.storage().persistent().remove(&(symbol_short!("QIDX"), epoch, last));
+        env.storage().persistent().remove(&(symbol_short!("QPOS"), epoch, tx_hash.clone()));
+        env.storage().persistent().remove(&(symbol_short!("QTX"), epoch, tx_hash.clone()));
+        env.storage().persistent().set(&(symbol_short!("QCOUNT"), epoch), &last);
+    }
+
+    fn _require_not_paused(env: &Env) {
+        if env.storage().instance().has(&symbol_short!("PAUSEDAT")) {
+            panic_with_error!(env, GovernanceError::TimelockPaused);
+        }
+    }
+
     /// Generate a unique hash for a transaction
+    /// @notice The preimage is domain-separated (scheme tag + this timelock's address) and covers
+    /// every field, with `data` length-prefixed so field boundaries are unambiguous
     fn _get_tx_hash(env: &Env, target: &Address, value: u128, data: &Bytes, eta: u64) -> Bytes {
-        // Create a simple hash by concatenating components
-        let mut hash_data = Bytes::new(env);
+        let mut hash_data = Bytes::from_slice(env, TX_HASH_DOMAIN);
+        hash_data.append(&env.current_contract_address().to_xdr(env));
+        hash_data.append(&target.to_xdr(env));
         hash_data.append(&Bytes::from_array(env, &value.to_be_bytes()));
+        hash_data.append(&Bytes::from_array(env, &data.len().to_be_bytes()));
         hash_data.append(data);
         hash_data.append(&Bytes::from_array(env, &eta.to_be_bytes()));
-        
+
         // Use keccak256 and convert hash to Bytes
         let hash = env.crypto().keccak256(&hash_data);
         Bytes::from_array(env, &hash.to_array())
     }
+
+    /// Hash used before target and domain were part of the preimage (kept for `migrate_queued`)
+    fn _get_legacy_tx_hash(env: &Env, value: u128, data: &Bytes, eta: u64) -> Bytes {
+        let mut hash_data = Bytes::new(env);
+        hash_data.append(&Bytes::from_array(env, &value.to_be_bytes()));
+        hash_data.append(data);
+        hash_data.append(&Bytes::from_array(env, &eta.to_be_bytes()));
+
+        let hash = env.crypto().keccak256(&hash_data);
+        Bytes::from_array(env, &hash.to_array())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use soroban_sdk::testutils::{Address as _, Ledger};
+    use soroban_sdk::IntoVal;
 
     #[test]
     fn test_initialize() {
@@ -256,5 +537,368 @@ mod tests {
         client.cancel_transaction(&target, &value, &data, &eta);
         assert!(!client.is_queued(&tx_hash));
     }
+
+    #[test]
+    fn test_emergency_pause_invalidates_queue() {
+        let env = Env::default();
+        env.ledger().with_mut(|li| {
+            li.timestamp = 1000000;
+        });
+
+        let contract_id = env.register_contract(None, Timelock);
+        let client = TimelockClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let guardian = Address::generate(&env);
+        let delay = 3600u64;
+
+        env.mock_all_auths();
+        client.initialize(&admin, &delay);
+        client.set_guardian(&guardian);
+        assert_eq!(client.get_guardian(), Some(guardian.clone()));
+
+        let target = Address::generate(&env);
+        let data = Bytes::new(&env);
+        let eta = 1000000 + delay + 100;
+        let tx_hash = client.queue_transaction(&target, &0u128, &data, &eta);
+
+        client.emergency_pause(&guardian);
+        assert!(client.is_paused());
+        assert!(!client.is_queued(&tx_hash));
+
+        // Nothing can be queued or executed while paused
+        env.ledger().with_mut(|li| {
+            li.timestamp = eta + 1;
+        });
+        assert_eq!(
+            client.try_execute_transaction(&target, &0u128, &data, &eta),
+            Err(Ok(GovernanceError::TimelockPaused.into()))
+        );
+        assert_eq!(
+            client.try_queue_transaction(&target, &0u128, &data, &(eta + delay)),
+            Err(Ok(GovernanceError::TimelockPaused.into()))
+        );
+    }
+
+    #[test]
+    fn test_unpause_requires_cooldown() {
+        let env = Env::default();
+        env.ledger().with_mut(|li| {
+            li.timestamp = 1000000;
+        });
+
+        let contract_id = env.register_contract(None, Timelock);
+        let client = TimelockClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let guardian = Address::generate(&env);
+        let delay = 3600u64;
+
+        env.mock_all_auths();
+        client.initialize(&admin, &delay);
+        client.set_guardian(&guardian);
+
+        let target = Address::generate(&env);
+        let data = Bytes::new(&env);
+        let eta = 1000000 + delay + 100;
+        let tx_hash = client.queue_transaction(&target, &0u128, &data, &eta);
+
+        client.emergency_pause(&guardian);
+        assert_eq!(client.try_unpause(), Err(Ok(GovernanceError::CooldownActive.into())));
+
+        env.ledger().with_mut(|li| {
+            li.timestamp = 1000000 + EMERGENCY_COOLDOWN;
+        });
+        client.unpause();
+        assert!(!client.is_paused());
+
+        // Transactions invalidated by the pause stay invalid until re-queued
+        assert!(!client.is_queued(&tx_hash));
+        let new_eta = 1000000 + EMERGENCY_COOLDOWN + delay;
+        let tx_hash = client.queue_transaction(&target, &0u128, &data, &new_eta);
+        assert!(client.is_queued(&tx_hash));
+    }
+
+    #[test]
+    fn test_emergency_pause_guardian_only() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, Timelock);
+        let client = TimelockClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let guardian = Address::generate(&env);
+
+        env.mock_all_auths();
+        client.initialize(&admin, &3600u64);
+
+        // No guardian configured yet
+        assert_eq!(
+            client.try_emergency_pause(&guardian),
+            Err(Ok(GovernanceError::Unauthorized.into()))
+        );
+
+        client.set_guardian(&guardian);
+        assert_eq!(
+            client.try_emergency_pause(&admin),
+            Err(Ok(GovernanceError::Unauthorized.into()))
+        );
+        assert!(!client.is_paused());
+    }
+
+    #[test]
+    fn test_list_queued() {
+        let env = Env::default();
+        env.ledger().with_mut(|li| {
+            li.timestamp = 1000000;
+        });
+
+        let contract_id = env.register_contract(None, Timelock);
+        let client = TimelockClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let delay = 3600u64;
+
+        env.mock_all_auths();
+        client.initialize(&admin, &delay);
+
+        let token = Address::generate(&env);
+        let recipient = Address::generate(&env);
+        let transfer_data = proposal_actions::encode_call(
+            &env,
+            symbol_short!("transfer"),
+            (admin.clone(), recipient, 500i128).into_val(&env),
+        );
+        let raw_target = Address::generate(&env);
+        let raw_data = Bytes::from_array(&env, &[0xde, 0xad]);
+        let eta = 1000000 + delay + 100;
+
+        let transfer_hash = client.queue_transaction(&token, &0u128, &transfer_data, &eta);
+        let raw_hash = client.queue_transaction(&raw_target, &7u128, &raw_data, &eta);
+        // Re-queueing an identical transaction doesn't duplicate it
+        client.queue_transaction(&token, &0u128, &transfer_data, &eta);
+        assert_eq!(client.queued_count(), 2);
+
+        let queued = client.list_queued(&0, &10);
+        let transfer = queued.get(0).unwrap();
+        assert_eq!(transfer.tx_hash, transfer_hash);
+        assert_eq!(transfer.target, token);
+        assert_eq!(transfer.eta, eta);
+        assert_eq!(transfer.function, Some(symbol_short!("transfer")));
+        assert_eq!(transfer.arg_count, 3);
+        let raw = queued.get(1).unwrap();
+        assert_eq!(raw.tx_hash, raw_hash);
+        assert_eq!(raw.value, 7);
+        assert_eq!(raw.function, None);
+
+        assert_eq!(client.list_queued(&1, &10).len(), 1);
+        assert_eq!(client.list_queued(&5, &10).len(), 0);
+
+        // Cancel and execute both drop entries
+        client.cancel_transaction(&token, &0u128, &transfer_data, &eta);
+        assert_eq!(client.queued_count(), 1);
+        assert_eq!(client.list_queued(&0, &10).get(0).unwrap().tx_hash, raw_hash);
+
+        env.ledger().with_mut(|li| {
+            li.timestamp = eta + 1;
+        });
+        client.execute_transaction(&raw_target, &7u128, &raw_data, &eta);
+        assert_eq!(client.queued_count(), 0);
+    }
+
+    #[test]
+    fn test_list_queued_cleared_by_emergency_pause() {
+        let env = Env::default();
+        env.ledger().with_mut(|li| {
+            li.timestamp = 1000000;
+        });
+
+        let contract_id = env.register_contract(None, Timelock);
+        let client = TimelockClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let guardian = Address::generate(&env);
+
+        env.mock_all_auths();
+        client.initialize(&admin, &3600u64);
+        client.set_guardian(&guardian);
+
+        let target = Address::generate(&env);
+        let data = Bytes::new(&env);
+        client.queue_transaction(&target, &0u128, &data, &(1000000 + 3700));
+        assert_eq!(client.queued_count(), 1);
+
+        client.emergency_pause(&guardian);
+        assert_eq!(client.queued_count(), 0);
+        assert_eq!(client.list_queued(&0, &10).len(), 0);
+
+        // A voided transaction can no longer be canceled out of the index
+        client.cancel_transaction(&target, &0u128, &data, &(1000000 + 3700));
+        assert_eq!(client.queued_count(), 0);
+    }
+
+    #[test]
+    fn test_voided_queue_index_is_pruned_in_batches() {
+        let env = Env::default();
+        env.ledger().with_mut(|li| {
+            li.timestamp = 1000000;
+        });
+
+        let contract_id = env.register_contract(None, Timelock);
+        let client = TimelockClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let guardian = Address::generate(&env);
+
+        env.mock_all_auths();
+        client.initialize(&admin, &3600u64);
+        client.set_guardian(&guardian);
+
+        let target = Address::generate(&env);
+        let data = Bytes::new(&env);
+        let stale_eta = 1000000 + 3700;
+        let stale_hash = client.queue_transaction(&target, &0u128, &data, &stale_eta);
+        client.queue_transaction(&target, &1u128, &data, &stale_eta);
+
+        // The pause only bumps the epoch; the voided index stays until pruned
+        client.emergency_pause(&guardian);
+        assert_eq!(client.queued_count(), 0);
+        assert_eq!(client.try_prune_voided(&1, &10), Err(Ok(GovernanceError::InvalidState.into())));
+
+        assert_eq!(client.prune_voided(&0, &1), 1);
+        assert_eq!(client.prune_voided(&0, &10), 0);
+        env.as_contract(&contract_id, || {
+            assert!(!env.storage().persistent().has(&(symbol_short!("QPOS"), 0u32, stale_hash.clone())));
+            assert!(!env.storage().persistent().has(&(symbol_short!("QTX"), 0u32, stale_hash.clone())));
+            assert!(!env.storage().persistent().has(&(symbol_short!("QIDX"), 0u32, 0u32)));
+            assert!(!env.storage().persistent().has(&(symbol_short!("QUEUED"), stale_hash.clone())));
+        });
+
+        env.ledger().with_mut(|li| {
+            li.timestamp += EMERGENCY_COOLDOWN;
+        });
+        client.unpause();
+        assert_eq!(client.queued_count(), 0);
+        assert_eq!(client.list_queued(&0, &10).len(), 0);
+
+        // Only transactions queued after the pause are listed
+        let eta = env.ledger().timestamp() + 3700;
+        let tx_hash = client.queue_transaction(&target, &2u128, &data, &eta);
+        let queued = client.list_queued(&0, &10);
+        assert_eq!(queued.len(), 1);
+        assert_eq!(queued.get(0).unwrap().tx_hash, tx_hash);
+
+        // Canceling a voided transaction leaves the new epoch's index intact
+        client.cancel_transaction(&target, &0u128, &data, &stale_eta);
+        assert_eq!(client.queued_count(), 1);
+    }
+
+    #[test]
+    fn test_tx_hash_commits_to_target() {
+        let env = Env::default();
+        env.ledger().with_mut(|li| {
+            li.timestamp = 1000000;
+        });
+
+        let contract_id = env.register_contract(None, Timelock);
+        let client = TimelockClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let delay = 3600u64;
+
+        env.mock_all_auths();
+        client.initialize(&admin, &delay);
+
+        // Identical value/data/eta aimed at different targets used to share one hash
+        let target_a = Address::generate(&env);
+        let target_b = Address::generate(&env);
+        let data = Bytes::from_array(&env, &[1, 2, 3]);
+        let eta = 1000000 + delay + 100;
+
+        let hash_a = client.queue_transaction(&target_a, &0u128, &data, &eta);
+        let hash_b = client.queue_transaction(&target_b, &0u128, &data, &eta);
+        assert_ne!(hash_a, hash_b);
+        assert_eq!(client.queued_count(), 2);
+
+        // Canceling one leaves the other queued
+        client.cancel_transaction(&target_a, &0u128, &data, &eta);
+        assert!(!client.is_queued(&hash_a));
+        assert!(client.is_queued(&hash_b));
+
+        env.ledger().with_mut(|li| {
+            li.timestamp = eta + 1;
+        });
+        client.execute_transaction(&target_b, &0u128, &data, &eta);
+        assert!(!client.is_queued(&hash_b));
+
+        // A canceled transaction can't be executed through the other target's entry
+        let result = client.try_execute_transaction(&target_a, &0u128, &data, &eta);
+        assert_eq!(result, Err(Ok(GovernanceError::InvalidState.into())));
+    }
+
+    #[test]
+    fn test_tx_hash_domain_separated_per_timelock() {
+        let env = Env::default();
+        env.ledger().with_mut(|li| {
+            li.timestamp = 1000000;
+        });
+        env.mock_all_auths();
+
+        let admin = Address::generate(&env);
+        let first = TimelockClient::new(&env, &env.register_contract(None, Timelock));
+        let second = TimelockClient::new(&env, &env.register_contract(None, Timelock));
+        first.initialize(&admin, &3600u64);
+        second.initialize(&admin, &3600u64);
+
+        let target = Address::generate(&env);
+        let data = Bytes::new(&env);
+        let eta = 1000000 + 3700;
+        assert_ne!(
+            first.queue_transaction(&target, &0u128, &data, &eta),
+            second.queue_transaction(&target, &0u128, &data, &eta)
+        );
+    }
+
+    #[test]
+    fn test_migrate_legacy_queued_entry() {
+        let env = Env::default();
+        env.ledger().with_mut(|li| {
+            li.timestamp = 1000000;
+        });
+
+        let contract_id = env.register_contract(None, Timelock);
+        let client = TimelockClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        env.mock_all_auths();
+        client.initialize(&admin, &3600u64);
+
+        let target = Address::generate(&env);
+        let data = Bytes::from_array(&env, &[4, 5, 6]);
+        let eta = 1000000 + 3700;
+
+        // Entry written by the previous contract version
+        let legacy_hash = env.as_contract(&contract_id, || {
+            let legacy_hash = Timelock::_get_legacy_tx_hash(&env, 0, &data, eta);
+            env.storage().persistent().set(&(symbol_short!("QUEUED"), legacy_hash.clone()), &true);
+            legacy_hash
+        });
+        let tx_hash = env.as_contract(&contract_id, || Timelock::_get_tx_hash(&env, &target, 0, &data, eta));
+        assert!(!client.is_queued(&tx_hash));
+
+        assert!(client.migrate_queued(&target, &0u128, &data, &eta));
+        assert!(client.is_queued(&tx_hash));
+        assert_eq!(client.list_queued(&0, &10).get(0).unwrap().tx_hash, tx_hash);
+        env.as_contract(&contract_id, || {
+            assert!(!env.storage().persistent().has(&(symbol_short!("QUEUED"), legacy_hash.clone())));
+        });
+
+        // Nothing left to migrate, and the migrated entry executes normally
+        assert!(!client.migrate_queued(&target, &0u128, &data, &eta));
+        env.ledger().with_mut(|li| {
+            li.timestamp = eta + 1;
+        });
+        client.execute_transaction(&target, &0u128, &data, &eta);
+        assert_eq!(client.queued_count(), 0);
+    }
 }
 
@@ -1,5 +1,5 @@
 #![allow(unused)]
-use soroban_sdk::{contracttype, Address, Bytes, Env, String, Vec};
+use soroban_sdk::{contracttype, Address, Bytes, BytesN, Env, String, Symbol, Vec};
 
 /// Proposal states
 #[contracttype]
@@ -26,6 +26,15 @@ pub enum VoteSupport {
     Abstain = 2,
 }
 
+/// How voting power is turned into tally weight
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum VotingStrategy {
+    Linear = 0,    // one token, one vote
+    Quadratic = 1, // weight = floor(sqrt(voting power)) per address; not sybil-resistant
+}
+
 /// Checkpoint for tracking historical voting power
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -34,6 +43,16 @@ pub struct Checkpoint {
     pub votes: u128,
 }
 
+/// Correction to the votes of a checkpoint from `from_block` until the next checkpoint
+/// (a settled delegation expiry that fell between two checkpoints)
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VoteAdjustment {
+    pub from_block: u64,
+    pub amount: u128,
+    pub increase: bool,
+}
+
 /// Proposal structure
 #[contracttype]
 #[derive(Clone, Debug)]
@@ -54,6 +73,16 @@ pub struct Proposal {
     pub eta: u64, // execution time after timelock
 }
 
+/// Pointer from a proposal to its off-chain deliberation thread
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DiscussionLink {
+    pub url_hash: BytesN<32>, // sha256 of the canonical discussion URL, checked off-chain against `uri`
+    pub uri: String,
+    pub linked_by: Address,
+    pub linked_at: u64,       // block number
+}
+
 /// Vote record for a proposal
 #[contracttype]
 #[derive(Clone, Debug)]
@@ -74,6 +103,64 @@ pub struct GovernanceConfig {
     pub timelock_delay: u64,    // seconds to wait before execution
 }
 
+/// Treasury payout executed by the timelock (which holds governance funds)
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TreasuryTransfer {
+    pub token: Address,     // token contract to transfer from the treasury
+    pub recipient: Address,
+    pub amount: i128,
+}
+
+/// WASM upgrade of a governed contract
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ContractUpgrade {
+    pub contract: Address,
+    pub new_wasm_hash: BytesN<32>,
+}
+
+/// Typed proposal payloads, encoded into `targets/values/calldatas` by the proposal builder
+#[contracttype]
+#[derive(Clone, Debug)]
+pub enum ProposalAction {
+    UpdateConfig(GovernanceConfig),
+    TreasuryTransfer(TreasuryTransfer),
+    UpgradeContract(ContractUpgrade),
+}
+
+/// Pending timelock transaction, as listed by `Timelock::list_queued`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct QueuedTransaction {
+    pub tx_hash: Bytes,
+    pub target: Address,
+    pub value: u128,
+    pub eta: u64,
+    pub function: Option<Symbol>, // decoded from the calldata; None if it isn't a `proposal_actions` encoding
+    pub arg_count: u32,
+}
+
+/// Participation rewards configuration
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RewardsConfig {
+    pub reward_per_proposal: i128, // max reward token amount allocated to each concluded proposal
+    pub min_votes: u128,           // anti-sybil: minimum voting power a voter needs to claim
+    pub claim_period: u64,         // blocks after the proposal's end_block during which claims are open
+}
+
+/// Rewards allocated to a concluded proposal
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RewardPool {
+    pub amount: i128,      // total allocated from the pot
+    pub total_votes: u128, // votes tallied on the proposal (shares are pro-rata to this)
+    pub claimed: i128,
+    pub deadline: u64,     // last block at which claims are accepted
+    pub swept: bool,
+}
+
 /// Error types for governance contracts
 #[contracttype]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -93,6 +180,13 @@ pub enum GovernanceError {
     QuorumNotReached = 12,
     InvalidCheckpoint = 13,
     ArrayLengthMismatch = 14,
+    AlreadyClaimed = 16,
+    ClaimWindowClosed = 17,
+    BelowRewardThreshold = 18,
+    TimelockPaused = 19,
+    CooldownActive = 20,
+    InvalidExpiry = 21,
+    TimedDelegationLimit = 22,
 }
 
 impl From<GovernanceError> for soroban_sdk::Error {
@@ -1,7 +1,9 @@
 #![allow(unused)]
+#![allow(clippy::too_many_arguments)]
 use crate::types::{GovernanceError, Proposal, VoteRecord, VoteSupport};
 use crate::governance_token::GovernanceTokenClient;
-use soroban_sdk::{contract, contractimpl, panic_with_error, symbol_short, Address, Env};
+use crate::proposal_manager::ProposalManagerClient;
+use soroban_sdk::{contract, contractimpl, panic_with_error, symbol_short, Address, Env, IntoVal};
 
 /// Voting System Contract - handles vote casting and tallying
 #[contract]
@@ -26,40 +28,28 @@ impl VotingSystem {
         support: VoteSupport,
     ) -> VoteRecord {
         voter.require_auth();
+        Self::_cast_vote(&env, &token_contract, proposal_id, snapshot_block, &voter, support)
+    }
 
-        // Check if already voted
-        if Self::has_voted(env.clone(), proposal_id, voter.clone()) {
-            panic_with_error!(&env, GovernanceError::AlreadyVoted);
-        }
-
-        // Get voting power at snapshot block (proposal start block)
-        // This prevents flash loan attacks and vote manipulation
-        let token_client = GovernanceTokenClient::new(&env, &token_contract);
-        let votes = token_client.get_prior_votes(&voter, &snapshot_block);
-
-        if votes == 0 {
-            panic_with_error!(&env, GovernanceError::InsufficientBalance);
-        }
-
-        // Record the vote
-        let vote_record = VoteRecord {
-            has_voted: true,
-            support: support.clone(),
-            votes,
-        };
-
-        env.storage().persistent().set(
-            &(symbol_short!("VOTE"), proposal_id, voter.clone()),
-            &vote_record
-        );
+    /// Cast a vote authorized off-chain by the voter
+    /// @notice Lets a relayer pay for the vote. The voter signs a Soroban authorization entry for
+    /// `(token_contract, proposal_id, snapshot_block, support)`; the host checks the signature and
+    /// its nonce, so any account type works and a signed vote can't be replayed or altered.
+    /// Voting power is checked at the snapshot block exactly as in `cast_vote`.
+    pub fn cast_vote_by_sig(
+        env: Env,
+        token_contract: Address,
+        proposal_id: u64,
+        snapshot_block: u64,
+        voter: Address,
+        support: VoteSupport,
+    ) -> VoteRecord {
+        voter.require_auth_for_args((token_contract.clone(), proposal_id, snapshot_block, support).into_val(&env));
 
-        // Emit VoteCast event
-        env.events().publish(
-            (symbol_short!("VoteCast"), voter.clone()),
-            (proposal_id, support as u32, votes)
-        );
+        // Emit VoteCastBySig event
+        env.events().publish((symbol_short!("VoteSig"), voter.clone()), proposal_id);
 
-        vote_record
+        Self::_cast_vote(&env, &token_contract, proposal_id, snapshot_block, &voter, support)
     }
 
     /// Cast vote with reason (for transparency)
@@ -83,6 +73,21 @@ impl VotingSystem {
         vote_record
     }
 
+    /// Submit a cast vote to the proposal manager's tally
+    /// @notice ProposalManager only accepts tallies from its voting contract, so votes reach it
+    /// through this relay. Anyone may submit; the manager counts each voter once and rejects
+    /// receipts whose weight doesn't match its own token at the proposal's start block.
+    /// @param proposal_manager: Address of the ProposalManager contract
+    /// @param proposal_id: The proposal ID
+    /// @param voter: The address whose vote is submitted
+    pub fn submit_vote(env: Env, proposal_manager: Address, proposal_id: u64, voter: Address) {
+        let receipt = Self::get_receipt(env.clone(), proposal_id, voter.clone())
+            .unwrap_or_else(|| panic_with_error!(&env, GovernanceError::InvalidState));
+
+        let manager_client = ProposalManagerClient::new(&env, &proposal_manager);
+        manager_client.record_vote(&proposal_id, &voter, &receipt.support, &receipt.votes);
+    }
+
     /// Check if an address has voted on a proposal
     pub fn has_voted(env: Env, proposal_id: u64, voter: Address) -> bool {
         env.storage()
@@ -164,14 +169,101 @@ impl VotingSystem {
         let total_votes = for_votes + against_votes + abstain_votes;
         ((total_votes * 100) / total_supply) as u64
     }
+
+    // ========== INTERNAL HELPER FUNCTIONS ==========
+
+    /// Record a vote at snapshot voting power; callers handle authorization
+    fn _cast_vote(
+        env: &Env,
+        token_contract: &Address,
+        proposal_id: u64,
+        snapshot_block: u64,
+        voter: &Address,
+        support: VoteSupport,
+    ) -> VoteRecord {
+        // Check if already voted
+        if Self::has_voted(env.clone(), proposal_id, voter.clone()) {
+            panic_with_error!(env, GovernanceError::AlreadyVoted);
+        }
+
+        // Get voting power at snapshot block (proposal start block)
+        // This prevents flash loan attacks and vote manipulation
+        let token_client = GovernanceTokenClient::new(env, token_contract);
+        let votes = token_client.get_prior_votes(voter, &snapshot_block);
+
+        if votes == 0 {
+            panic_with_error!(env, GovernanceError::InsufficientBalance);
+        }
+
+        // Record the vote
+        let vote_record = VoteRecord {
+            has_voted: true,
+            support: support.clone(),
+            votes,
+        };
+
+        env.storage().persistent().set(
+            &(symbol_short!("VOTE"), proposal_id, voter.clone()),
+            &vote_record
+        );
+
+        // Emit VoteCast event
+        env.events().publish(
+            (symbol_short!("VoteCast"), voter.clone()),
+            (proposal_id, support as u32, votes)
+        );
+
+        vote_record
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    extern crate std;
+
     use super::*;
     use crate::governance_token::{GovernanceToken, GovernanceTokenClient};
-    use soroban_sdk::testutils::{Address as _, Ledger};
-    use soroban_sdk::{Bytes, String, Vec};
+    use soroban_sdk::testutils::{Address as _, AuthorizedFunction, AuthorizedInvocation, Ledger, MockAuth, MockAuthInvoke};
+    use soroban_sdk::{Bytes, String, Symbol, Vec};
+
+    /// Token held by a voter, and a voting system with proposals 1 and 2
+    fn setup_relayed_voting(env: &Env) -> (Address, Address, VotingSystemClient<'_>) {
+        env.ledger().with_mut(|li| li.sequence_number = 100);
+
+        let voter = Address::generate(env);
+
+        let token_id = env.register_contract(None, GovernanceToken);
+        let token_client = GovernanceTokenClient::new(env, &token_id);
+        env.mock_all_auths();
+        token_client.initialize(
+            &voter,
+            &String::from_str(env, "Gov"),
+            &String::from_str(env, "GOV"),
+            &18,
+            &1_000_000u128
+        );
+
+        let voting_id = env.register_contract(None, VotingSystem);
+        let voting_client = VotingSystemClient::new(env, &voting_id);
+        create_test_proposal(env, &voting_id, 1, 100, 200);
+        create_test_proposal(env, &voting_id, 2, 100, 200);
+
+        env.ledger().with_mut(|li| li.sequence_number = 150);
+        (voter, token_id, voting_client)
+    }
+
+    /// Voter's authorization of a vote, as a relayer would attach it
+    fn authorize_vote(env: &Env, voting_client: &VotingSystemClient, voter: &Address, token_id: &Address, proposal_id: u64, support: VoteSupport) {
+        env.mock_auths(&[MockAuth {
+            address: voter,
+            invoke: &MockAuthInvoke {
+                contract: &voting_client.address,
+                fn_name: "cast_vote_by_sig",
+                args: (token_id.clone(), proposal_id, 100u64, support).into_val(env),
+                sub_invokes: &[],
+            },
+        }]);
+    }
 
     fn create_test_proposal(env: &Env, contract_id: &Address, proposal_id: u64, start_block: u64, end_block: u64) {
         let proposer = Address::generate(env);
@@ -354,5 +446,56 @@ mod tests {
 
         assert!(voting_client.proposal_succeeded(&token_id, &vote_for.votes, &vote_against.votes, &0u128, &quorum_numerator));
     }
-}
 
+    #[test]
+    fn test_cast_vote_by_sig() {
+        let env = Env::default();
+        let (voter, token_id, voting_client) = setup_relayed_voting(&env);
+
+        authorize_vote(&env, &voting_client, &voter, &token_id, 1, VoteSupport::For);
+        let vote_record = voting_client.cast_vote_by_sig(&token_id, &1u64, &100u64, &voter, &VoteSupport::For);
+
+        // The voter authorized exactly this vote
+        assert_eq!(
+            env.auths(),
+            std::vec![(
+                voter.clone(),
+                AuthorizedInvocation {
+                    function: AuthorizedFunction::Contract((
+                        voting_client.address.clone(),
+                        Symbol::new(&env, "cast_vote_by_sig"),
+                        (token_id.clone(), 1u64, 100u64, VoteSupport::For).into_val(&env),
+                    )),
+                    sub_invocations: std::vec![],
+                }
+            )]
+        );
+        assert_eq!(vote_record.support, VoteSupport::For);
+        assert_eq!(vote_record.votes, 1_000_000u128);
+        assert!(voting_client.has_voted(&1u64, &voter));
+
+        // The authorization doesn't carry over to another proposal
+        assert!(voting_client.try_cast_vote_by_sig(&token_id, &2u64, &100u64, &voter, &VoteSupport::For).is_err());
+        assert!(!voting_client.has_voted(&2u64, &voter));
+
+        // Already-voted checks still apply to relayed votes
+        authorize_vote(&env, &voting_client, &voter, &token_id, 1, VoteSupport::Against);
+        assert_eq!(
+            voting_client.try_cast_vote_by_sig(&token_id, &1u64, &100u64, &voter, &VoteSupport::Against).err(),
+            Some(Ok(GovernanceError::AlreadyVoted.into()))
+        );
+    }
+
+    #[test]
+    fn test_cast_vote_by_sig_rejects_altered_vote() {
+        let env = Env::default();
+        let (voter, token_id, voting_client) = setup_relayed_voting(&env);
+        let bogus_token = Address::generate(&env);
+
+        // The relayer flips the authorized For into Against, or swaps the token
+        authorize_vote(&env, &voting_client, &voter, &token_id, 1, VoteSupport::For);
+        assert!(voting_client.try_cast_vote_by_sig(&token_id, &1u64, &100u64, &voter, &VoteSupport::Against).is_err());
+        assert!(voting_client.try_cast_vote_by_sig(&bogus_token, &1u64, &100u64, &voter, &VoteSupport::For).is_err());
+        assert!(!voting_client.has_voted(&1u64, &voter));
+    }
+}
@@ -0,0 +1,20 @@
+use soroban_sdk::contracterror;
+
+/// Errors returned by the reputation oracle
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum OracleError {
+    /// Contract already initialized
+    AlreadyInitialized = 1,
+    /// Caller is not the admin
+    Unauthorized = 2,
+    /// Batch was signed by a key that is not registered
+    UnknownSigner = 3,
+    /// Batch nonce is not greater than the signer's last accepted nonce
+    StaleNonce = 4,
+    /// Batch contains no updates
+    EmptyBatch = 5,
+    /// Batch contains more than `MAX_BATCH_SIZE` updates
+    BatchTooLarge = 6,
+}
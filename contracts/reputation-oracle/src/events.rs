@@ -0,0 +1,28 @@
+use soroban_sdk::{symbol_short, Address, BytesN, Env, Symbol};
+
+use crate::storage::ReputationRecord;
+
+const TOPIC_SIGNER_ADDED: Symbol = symbol_short!("sgn_add");
+const TOPIC_SIGNER_REMOVED: Symbol = symbol_short!("sgn_rm");
+const TOPIC_BATCH: Symbol = symbol_short!("batch");
+const TOPIC_REPUTATION_UPDATE: Symbol = symbol_short!("rep_upd");
+
+pub fn emit_signer_added(env: &Env, signer: &BytesN<32>) {
+    env.events().publish((TOPIC_SIGNER_ADDED, signer.clone()), ());
+}
+
+pub fn emit_signer_removed(env: &Env, signer: &BytesN<32>) {
+    env.events().publish((TOPIC_SIGNER_REMOVED, signer.clone()), ());
+}
+
+pub fn emit_batch(env: &Env, signer: &BytesN<32>, nonce: u64, count: u32) {
+    env.events()
+        .publish((TOPIC_BATCH, signer.clone()), (nonce, count));
+}
+
+pub fn emit_reputation_updated(env: &Env, account: &Address, record: &ReputationRecord, reputation: u32) {
+    env.events().publish(
+        (TOPIC_REPUTATION_UPDATE, account.clone()),
+        (record.quest_completions, record.endorsements, reputation),
+    );
+}
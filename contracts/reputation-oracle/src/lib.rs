@@ -0,0 +1,139 @@
+#![no_std]
+
+use soroban_sdk::{contract, contractimpl, panic_with_error, xdr::ToXdr, Address, Bytes, BytesN, Env};
+
+mod errors;
+mod events;
+mod storage;
+
+pub use crate::errors::OracleError;
+pub use crate::storage::{ReputationBatch, ReputationRecord, ReputationUpdate};
+
+use crate::events::{emit_batch, emit_reputation_updated, emit_signer_added, emit_signer_removed};
+
+/// Domain prefix of every signed batch payload
+pub const BATCH_DOMAIN: &[u8] = b"chenaikit:reputation:batch:v1";
+/// Maximum number of updates in one batch
+pub const MAX_BATCH_SIZE: u32 = 50;
+/// Upper bound of the reputation score
+pub const MAX_REPUTATION: u32 = 1_000;
+/// Reputation points per completed quest
+pub const QUEST_WEIGHT: u32 = 20;
+/// Reputation points per endorsement
+pub const ENDORSEMENT_WEIGHT: u32 = 5;
+
+#[contract]
+pub struct ReputationOracle;
+
+#[contractimpl]
+impl ReputationOracle {
+    /// Initialize the oracle with an admin that manages the signer set
+    pub fn initialize(env: Env, admin: Address) {
+        if storage::has_admin(&env) {
+            panic_with_error!(&env, OracleError::AlreadyInitialized);
+        }
+        admin.require_auth();
+        storage::set_admin(&env, &admin);
+    }
+
+    /// Authorize a backend ed25519 key to sign batches (admin only)
+    pub fn add_signer(env: Env, admin: Address, signer: BytesN<32>) {
+        Self::require_admin(&env, &admin);
+        storage::add_signer(&env, &signer);
+        emit_signer_added(&env, &signer);
+    }
+
+    /// Revoke a backend key (admin only); its nonce is kept so old batches can't be replayed on re-add
+    pub fn remove_signer(env: Env, admin: Address, signer: BytesN<32>) {
+        Self::require_admin(&env, &admin);
+        storage::remove_signer(&env, &signer);
+        emit_signer_removed(&env, &signer);
+    }
+
+    /// Check whether a key may sign batches
+    pub fn is_signer(env: Env, signer: BytesN<32>) -> bool {
+        storage::is_signer(&env, &signer)
+    }
+
+    /// Last nonce accepted from a signer; the next batch must use a greater one
+    pub fn get_nonce(env: Env, signer: BytesN<32>) -> u64 {
+        storage::get_nonce(&env, &signer)
+    }
+
+    /// Bytes a signer must sign for `batch`: `BATCH_DOMAIN ‖ oracle address ‖ batch` (XDR)
+    pub fn batch_payload(env: Env, batch: ReputationBatch) -> Bytes {
+        let mut payload = Bytes::from_slice(&env, BATCH_DOMAIN);
+        payload.append(&env.current_contract_address().to_xdr(&env));
+        payload.append(&batch.to_xdr(&env));
+        payload
+    }
+
+    /// Apply a signed batch of reputation updates. Anyone may relay it; the signature is the authorization.
+    pub fn submit_batch(env: Env, signer: BytesN<32>, batch: ReputationBatch, signature: BytesN<64>) {
+        if !storage::is_signer(&env, &signer) {
+            panic_with_error!(&env, OracleError::UnknownSigner);
+        }
+        if batch.updates.is_empty() {
+            panic_with_error!(&env, OracleError::EmptyBatch);
+        }
+        if batch.updates.len() > MAX_BATCH_SIZE {
+            panic_with_error!(&env, OracleError::BatchTooLarge);
+        }
+        if batch.nonce <= storage::get_nonce(&env, &signer) {
+            panic_with_error!(&env, OracleError::StaleNonce);
+        }
+
+        // Traps on an invalid signature
+        let payload = Self::batch_payload(env.clone(), batch.clone());
+        env.crypto().ed25519_verify(&signer, &payload, &signature);
+
+        storage::set_nonce(&env, &signer, batch.nonce);
+
+        let now = env.ledger().timestamp();
+        for update in batch.updates.iter() {
+            let mut record = storage::get_record(&env, &update.account).unwrap_or(ReputationRecord {
+                quest_completions: 0,
+                endorsements: 0,
+                updated_at: now,
+            });
+            record.quest_completions = record.quest_completions.saturating_add(update.quest_completions);
+            record.endorsements = record.endorsements.saturating_add(update.endorsements);
+            record.updated_at = now;
+
+            storage::set_record(&env, &update.account, &record);
+            emit_reputation_updated(&env, &update.account, &record, Self::score(&record));
+        }
+
+        emit_batch(&env, &signer, batch.nonce, batch.updates.len());
+    }
+
+    /// Reputation of an account in `0..=MAX_REPUTATION` (0 if never updated)
+    pub fn get_reputation(env: Env, account: Address) -> u32 {
+        storage::get_record(&env, &account)
+            .map(|record| Self::score(&record))
+            .unwrap_or(0)
+    }
+
+    /// Raw accumulated inputs for an account
+    pub fn get_record(env: Env, account: Address) -> Option<ReputationRecord> {
+        storage::get_record(&env, &account)
+    }
+
+    fn score(record: &ReputationRecord) -> u32 {
+        record
+            .quest_completions
+            .saturating_mul(QUEST_WEIGHT)
+            .saturating_add(record.endorsements.saturating_mul(ENDORSEMENT_WEIGHT))
+            .min(MAX_REPUTATION)
+    }
+
+    fn require_admin(env: &Env, admin: &Address) {
+        admin.require_auth();
+        if *admin != storage::get_admin(env) {
+            panic_with_error!(env, OracleError::Unauthorized);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test;
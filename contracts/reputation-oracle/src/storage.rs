@@ -0,0 +1,94 @@
+use soroban_sdk::{contracttype, Address, BytesN, Env, Vec};
+
+const YEAR_LEDGERS: u32 = 6_307_200; // ~1 year in ledgers
+
+#[contracttype]
+#[derive(Clone)]
+enum DataKey {
+    Admin,
+    Signer(BytesN<32>),
+    Nonce(BytesN<32>),
+    Record(Address),
+}
+
+/// Reputation delta for one account, produced by the backend
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReputationUpdate {
+    pub account: Address,
+    pub quest_completions: u32,
+    pub endorsements: u32,
+}
+
+/// A batch of updates signed by one oracle key
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReputationBatch {
+    pub nonce: u64,
+    pub updates: Vec<ReputationUpdate>,
+}
+
+/// Accumulated reputation inputs for an account
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReputationRecord {
+    pub quest_completions: u32,
+    pub endorsements: u32,
+    pub updated_at: u64,
+}
+
+pub fn has_admin(env: &Env) -> bool {
+    env.storage().instance().has(&DataKey::Admin)
+}
+
+pub fn get_admin(env: &Env) -> Address {
+    env.storage().instance().get(&DataKey::Admin).unwrap()
+}
+
+pub fn set_admin(env: &Env, admin: &Address) {
+    env.storage().instance().set(&DataKey::Admin, admin);
+}
+
+pub fn is_signer(env: &Env, signer: &BytesN<32>) -> bool {
+    env.storage()
+        .persistent()
+        .has(&DataKey::Signer(signer.clone()))
+}
+
+pub fn add_signer(env: &Env, signer: &BytesN<32>) {
+    let key = DataKey::Signer(signer.clone());
+    env.storage().persistent().set(&key, &true);
+    env.storage().persistent().extend_ttl(&key, YEAR_LEDGERS, YEAR_LEDGERS);
+}
+
+pub fn remove_signer(env: &Env, signer: &BytesN<32>) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::Signer(signer.clone()));
+}
+
+/// Last nonce accepted from a signer (0 if none yet)
+pub fn get_nonce(env: &Env, signer: &BytesN<32>) -> u64 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Nonce(signer.clone()))
+        .unwrap_or(0)
+}
+
+pub fn set_nonce(env: &Env, signer: &BytesN<32>, nonce: u64) {
+    let key = DataKey::Nonce(signer.clone());
+    env.storage().persistent().set(&key, &nonce);
+    env.storage().persistent().extend_ttl(&key, YEAR_LEDGERS, YEAR_LEDGERS);
+}
+
+pub fn get_record(env: &Env, account: &Address) -> Option<ReputationRecord> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Record(account.clone()))
+}
+
+pub fn set_record(env: &Env, account: &Address, record: &ReputationRecord) {
+    let key = DataKey::Record(account.clone());
+    env.storage().persistent().set(&key, record);
+    env.storage().persistent().extend_ttl(&key, YEAR_LEDGERS, YEAR_LEDGERS);
+}
@@ -0,0 +1,195 @@
+extern crate std;
+
+use crate::{
+    OracleError, ReputationBatch, ReputationOracle, ReputationOracleClient, ReputationUpdate, MAX_BATCH_SIZE,
+    MAX_REPUTATION,
+};
+use ed25519_dalek::{Signer, SigningKey};
+use soroban_sdk::{testutils::Address as _, vec, Address, BytesN, Env, Vec};
+
+struct Setup<'a> {
+    env: Env,
+    admin: Address,
+    key: SigningKey,
+    signer: BytesN<32>,
+    oracle: ReputationOracleClient<'a>,
+}
+
+/// Oracle with one registered backend key
+fn setup<'a>() -> Setup<'a> {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let oracle = ReputationOracleClient::new(&env, &env.register_contract(None, ReputationOracle));
+    oracle.initialize(&admin);
+
+    let key = SigningKey::from_bytes(&[7u8; 32]);
+    let signer = BytesN::from_array(&env, &key.verifying_key().to_bytes());
+    oracle.add_signer(&admin, &signer);
+
+    Setup { env, admin, key, signer, oracle }
+}
+
+fn update(account: &Address, quest_completions: u32, endorsements: u32) -> ReputationUpdate {
+    ReputationUpdate { account: account.clone(), quest_completions, endorsements }
+}
+
+fn sign(s: &Setup, key: &SigningKey, batch: &ReputationBatch) -> BytesN<64> {
+    let payload: std::vec::Vec<u8> = s.oracle.batch_payload(batch).iter().collect();
+    BytesN::from_array(&s.env, &key.sign(&payload).to_bytes())
+}
+
+#[test]
+fn test_submit_batch_accumulates_reputation() {
+    let s = setup();
+    let alice = Address::generate(&s.env);
+    let bob = Address::generate(&s.env);
+
+    let batch = ReputationBatch { nonce: 1, updates: vec![&s.env, update(&alice, 2, 3), update(&bob, 0, 1)] };
+    s.oracle.submit_batch(&s.signer, &batch, &sign(&s, &s.key, &batch));
+
+    // 2 quests * 20 + 3 endorsements * 5
+    assert_eq!(s.oracle.get_reputation(&alice), 55);
+    assert_eq!(s.oracle.get_reputation(&bob), 5);
+
+    let batch = ReputationBatch { nonce: 2, updates: vec![&s.env, update(&alice, 1, 0)] };
+    s.oracle.submit_batch(&s.signer, &batch, &sign(&s, &s.key, &batch));
+
+    let record = s.oracle.get_record(&alice).unwrap();
+    assert_eq!(record.quest_completions, 3);
+    assert_eq!(record.endorsements, 3);
+    assert_eq!(s.oracle.get_reputation(&alice), 75);
+    assert_eq!(s.oracle.get_nonce(&s.signer), 2);
+}
+
+#[test]
+fn test_unknown_account_has_zero_reputation() {
+    let s = setup();
+    let nobody = Address::generate(&s.env);
+
+    assert_eq!(s.oracle.get_reputation(&nobody), 0);
+    assert_eq!(s.oracle.get_record(&nobody), None);
+}
+
+#[test]
+fn test_reputation_is_capped() {
+    let s = setup();
+    let alice = Address::generate(&s.env);
+
+    let batch = ReputationBatch { nonce: 1, updates: vec![&s.env, update(&alice, u32::MAX, u32::MAX)] };
+    s.oracle.submit_batch(&s.signer, &batch, &sign(&s, &s.key, &batch));
+
+    assert_eq!(s.oracle.get_reputation(&alice), MAX_REPUTATION);
+}
+
+#[test]
+fn test_replayed_batch_is_rejected() {
+    let s = setup();
+    let alice = Address::generate(&s.env);
+
+    let batch = ReputationBatch { nonce: 5, updates: vec![&s.env, update(&alice, 1, 0)] };
+    let signature = sign(&s, &s.key, &batch);
+    s.oracle.submit_batch(&s.signer, &batch, &signature);
+
+    assert_eq!(
+        s.oracle.try_submit_batch(&s.signer, &batch, &signature),
+        Err(Ok(OracleError::StaleNonce.into()))
+    );
+
+    // Lower nonces are stale as well
+    let batch = ReputationBatch { nonce: 4, updates: vec![&s.env, update(&alice, 1, 0)] };
+    assert_eq!(
+        s.oracle.try_submit_batch(&s.signer, &batch, &sign(&s, &s.key, &batch)),
+        Err(Ok(OracleError::StaleNonce.into()))
+    );
+    assert_eq!(s.oracle.get_reputation(&alice), 20);
+}
+
+#[test]
+fn test_unregistered_signer_is_rejected() {
+    let s = setup();
+    let alice = Address::generate(&s.env);
+    let rogue = SigningKey::from_bytes(&[9u8; 32]);
+    let rogue_pk = BytesN::from_array(&s.env, &rogue.verifying_key().to_bytes());
+
+    let batch = ReputationBatch { nonce: 1, updates: vec![&s.env, update(&alice, 1, 0)] };
+    assert_eq!(
+        s.oracle.try_submit_batch(&rogue_pk, &batch, &sign(&s, &rogue, &batch)),
+        Err(Ok(OracleError::UnknownSigner.into()))
+    );
+
+    // Removed signers are rejected too
+    s.oracle.remove_signer(&s.admin, &s.signer);
+    assert!(!s.oracle.is_signer(&s.signer));
+    assert_eq!(
+        s.oracle.try_submit_batch(&s.signer, &batch, &sign(&s, &s.key, &batch)),
+        Err(Ok(OracleError::UnknownSigner.into()))
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_forged_signature_panics() {
+    let s = setup();
+    let alice = Address::generate(&s.env);
+    let rogue = SigningKey::from_bytes(&[9u8; 32]);
+
+    // Registered key, signature from a different key
+    let batch = ReputationBatch { nonce: 1, updates: vec![&s.env, update(&alice, 1, 0)] };
+    s.oracle.submit_batch(&s.signer, &batch, &sign(&s, &rogue, &batch));
+}
+
+#[test]
+#[should_panic]
+fn test_tampered_batch_panics() {
+    let s = setup();
+    let alice = Address::generate(&s.env);
+
+    let batch = ReputationBatch { nonce: 1, updates: vec![&s.env, update(&alice, 1, 0)] };
+    let signature = sign(&s, &s.key, &batch);
+
+    let tampered = ReputationBatch { nonce: 1, updates: vec![&s.env, update(&alice, 50, 0)] };
+    s.oracle.submit_batch(&s.signer, &tampered, &signature);
+}
+
+#[test]
+fn test_batch_size_limits() {
+    let s = setup();
+    let alice = Address::generate(&s.env);
+
+    let empty = ReputationBatch { nonce: 1, updates: Vec::new(&s.env) };
+    assert_eq!(
+        s.oracle.try_submit_batch(&s.signer, &empty, &sign(&s, &s.key, &empty)),
+        Err(Ok(OracleError::EmptyBatch.into()))
+    );
+
+    let mut updates = Vec::new(&s.env);
+    for _ in 0..=MAX_BATCH_SIZE {
+        updates.push_back(update(&alice, 1, 0));
+    }
+    let oversized = ReputationBatch { nonce: 1, updates };
+    assert_eq!(
+        s.oracle.try_submit_batch(&s.signer, &oversized, &sign(&s, &s.key, &oversized)),
+        Err(Ok(OracleError::BatchTooLarge.into()))
+    );
+}
+
+#[test]
+fn test_signer_management_is_admin_only() {
+    let s = setup();
+    let outsider = Address::generate(&s.env);
+    let other = BytesN::from_array(&s.env, &[1u8; 32]);
+
+    assert_eq!(
+        s.oracle.try_add_signer(&outsider, &other),
+        Err(Ok(OracleError::Unauthorized.into()))
+    );
+    assert_eq!(
+        s.oracle.try_initialize(&outsider),
+        Err(Ok(OracleError::AlreadyInitialized.into()))
+    );
+
+    s.oracle.add_signer(&s.admin, &other);
+    assert!(s.oracle.is_signer(&other));
+}